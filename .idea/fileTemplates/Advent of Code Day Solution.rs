@@ -1,9 +1,35 @@
+use crate::aoc::solution::{PartFn, Solution};
+use crate::aoc::Answer;
+use crate::common::parsing::{lines, parse_input, InputLine};
+use crate::common::Parse;
 use crate::Result;
 
-pub fn solve_part_1(challenge_data: String) -> Result<()> {
-    Ok(())
+pub struct Input {
+    lines: Vec<String>,
 }
 
-pub fn solve_part_2(challenge_data: String) -> Result<()> {
-    Ok(())
-}
\ No newline at end of file
+impl Parse for Input {
+    fn parse(input: &str) -> Result<Self> {
+        let line = |_: &InputLine, text: &str| Ok(String::from(text));
+        Ok(Input {
+            lines: parse_input(input, lines(line))?,
+        })
+    }
+}
+
+pub struct Day${DAY};
+
+impl Solution for Day${DAY} {
+    const YEAR: u16 = super::YEAR;
+    const DAY: u8 = ${DAY};
+    type Input = Input;
+    const PARTS: &'static [PartFn<Input>] = &[solve_part_1, solve_part_2];
+}
+
+fn solve_part_1(input: &Input) -> Result<Answer> {
+    Ok(input.lines.len().into())
+}
+
+fn solve_part_2(input: &Input) -> Result<Answer> {
+    Ok(input.lines.len().into())
+}
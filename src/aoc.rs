@@ -1,7 +1,39 @@
+mod answer;
+mod batch;
+mod bench;
+mod calendar;
 mod challenge;
+mod client;
+mod fetch;
+mod import;
+mod input;
 mod prompt;
+mod scaffold;
+mod show;
 mod solution;
+mod source;
+mod submit;
+#[cfg(test)]
+mod test_dir;
+#[cfg(test)]
+mod test_server;
+mod verify;
+mod watch;
 
-pub use challenge::{get_challenge, Challenge};
+pub use answer::Answer;
+pub use batch::run_batch_with_summary;
+pub use bench::run_bench_with_report;
+pub use calendar::run_calendar;
+pub use challenge::{Challenge, ChallengeKey};
+pub use fetch::run_fetch;
+pub use import::run_import;
 pub use prompt::{prompt_select_challenge_day, prompt_select_challenge_part};
-pub use solution::{get_challenge_solution, ChallengeSolution};
+pub use scaffold::run_new;
+pub use show::{run_list, run_show};
+pub use solution::solution_registry;
+#[cfg(test)]
+pub use source::MemorySource;
+pub use source::{get_challenge, open_challenge_source, ChallengeSource, CHALLENGES_ENV_VAR};
+pub use submit::run_submit;
+pub use verify::run_verification_with_summary;
+pub use watch::run_watch;
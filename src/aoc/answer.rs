@@ -0,0 +1,106 @@
+use std::fmt::{Display, Formatter};
+
+use serde::Serialize;
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum Answer {
+    Number(i64),
+    Text(String),
+    MultiLine(String),
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Number(n) => write!(f, "{}", n),
+            Answer::Text(text) => write!(f, "{}", text),
+            Answer::MultiLine(text) => write!(f, "\n{}", text),
+        }
+    }
+}
+
+impl From<i32> for Answer {
+    fn from(n: i32) -> Self {
+        Answer::Number(n as i64)
+    }
+}
+
+impl From<u32> for Answer {
+    fn from(n: u32) -> Self {
+        Answer::Number(n as i64)
+    }
+}
+
+impl From<i64> for Answer {
+    fn from(n: i64) -> Self {
+        Answer::Number(n)
+    }
+}
+
+impl From<u64> for Answer {
+    fn from(n: u64) -> Self {
+        match i64::try_from(n) {
+            Ok(n) => Answer::Number(n),
+            Err(_) => Answer::Text(n.to_string()),
+        }
+    }
+}
+
+impl From<usize> for Answer {
+    fn from(n: usize) -> Self {
+        Answer::from(n as u64)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(text: String) -> Self {
+        if text.contains('\n') {
+            Answer::MultiLine(text)
+        } else {
+            Answer::Text(text)
+        }
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(text: &str) -> Self {
+        Answer::from(String::from(text))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn numbers_display_without_decoration() {
+        assert_eq!(
+            "1592918715629",
+            Answer::from(1592918715629_usize).to_string()
+        );
+        assert_eq!("-3", Answer::from(-3).to_string());
+    }
+
+    #[test]
+    fn oversized_unsigned_numbers_are_kept_as_text() {
+        assert_eq!(
+            Answer::Text(String::from("18446744073709551615")),
+            Answer::from(u64::MAX)
+        );
+    }
+
+    #[test]
+    fn multi_line_answers_start_on_a_new_line() {
+        let answer = Answer::from("#..#\n.##.");
+        assert_eq!(Answer::MultiLine(String::from("#..#\n.##.")), answer);
+        assert_eq!("\n#..#\n.##.", answer.to_string());
+    }
+
+    #[test]
+    fn answers_serialize_as_plain_values() -> crate::Result<()> {
+        assert_eq!("42", serde_json::to_string(&Answer::from(42))?);
+        assert_eq!("\"abc\"", serde_json::to_string(&Answer::from("abc"))?);
+        Ok(())
+    }
+}
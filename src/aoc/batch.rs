@@ -0,0 +1,296 @@
+use std::ops::RangeInclusive;
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+
+use super::answer::Answer;
+use super::challenge::{Challenge, ChallengeKey, DEFAULT_EXAMPLE_NAME};
+use super::input::load_challenge;
+use super::solution::{resolve_dataset, solution_registry, ChallengeSolution, Dataset, Registry};
+use super::source::ChallengeSource;
+use crate::common::jobs::run_in_order;
+use crate::common::{describe_error, OutputFormat};
+use crate::{AppParams, Error, Result};
+
+pub struct BatchResult {
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+    pub dataset: String,
+    pub outcome: std::result::Result<Answer, String>,
+    pub duration: Duration,
+}
+
+/// One solution's result as it appears in JSON output.
+#[derive(Serialize)]
+struct BatchRecord<'a> {
+    year: u16,
+    day: u8,
+    part: u8,
+    dataset: &'a str,
+    answer: Option<&'a Answer>,
+    wall_time_ms: f64,
+    error: Option<&'a str>,
+}
+
+impl BatchResult {
+    fn record(&self) -> BatchRecord<'_> {
+        BatchRecord {
+            year: self.year,
+            day: self.day,
+            part: self.part,
+            dataset: &self.dataset,
+            answer: self.outcome.as_ref().ok(),
+            wall_time_ms: self.duration.as_secs_f64() * 1000.0,
+            error: self.outcome.as_ref().err().map(|message| message.as_str()),
+        }
+    }
+}
+
+/// A day's solution to run on one dataset, or the reason the day's challenge could not be loaded.
+enum BatchTask<'a> {
+    Solve {
+        parts: Vec<u8>,
+        solution: ChallengeSolution,
+        challenge: &'a Challenge,
+        dataset: Dataset,
+    },
+    LoadFailed {
+        key: ChallengeKey,
+        message: String,
+    },
+}
+
+/// Loads the challenges one at a time, since sources are not shared between threads, then runs
+/// each day's solution on `params.jobs` threads. Results are in day and part order either way.
+pub fn run_batch(
+    source: &dyn ChallengeSource,
+    solutions: &Registry,
+    days: RangeInclusive<u8>,
+    params: &AppParams,
+) -> Vec<BatchResult> {
+    let loaded: Vec<_> = days
+        .map(|day| ChallengeKey::new(params.year, day))
+        .filter_map(|key| solutions.get(key).map(|solution| (key, solution)))
+        .map(|(key, solution)| {
+            let loaded = load_challenge(source, key, params).and_then(|challenge| {
+                let dataset = resolve_dataset(&challenge, params)?;
+                Ok((challenge, dataset))
+            });
+            (key, solution, loaded)
+        })
+        .collect();
+    let tasks: Vec<BatchTask> = loaded
+        .iter()
+        .map(|(key, solution, loaded)| match loaded {
+            Ok((challenge, dataset)) => BatchTask::Solve {
+                parts: (1..=challenge.parts)
+                    .filter(|&part| params.challenge_part.is_none_or(|p| p as u8 == part))
+                    .filter(|&part| solution.has_part(part))
+                    .collect(),
+                solution: *solution,
+                challenge,
+                dataset: *dataset,
+            },
+            Err(err) => BatchTask::LoadFailed {
+                key: *key,
+                message: describe_error(err.as_ref()),
+            },
+        })
+        .collect();
+    run_in_order(&tasks, params.jobs, |task| run_task(task, params))
+        .into_iter()
+        .flatten()
+        .collect()
+}
+
+/// Parses the input once and solves every part from it. Each part's time includes the parse.
+fn run_task(task: &BatchTask, params: &AppParams) -> Vec<BatchResult> {
+    match task {
+        BatchTask::Solve {
+            parts,
+            solution,
+            challenge,
+            dataset,
+        } => {
+            let parse_start = Instant::now();
+            let input = solution
+                .parse(challenge.data_for(*dataset))
+                .map_err(|err| describe_error(err.as_ref()));
+            let parse_duration = parse_start.elapsed();
+            parts
+                .iter()
+                .map(|&part| {
+                    let start = Instant::now();
+                    let outcome = match &input {
+                        Ok(input) => solution
+                            .solve(part, input)
+                            .map_err(|err| describe_error(err.as_ref())),
+                        Err(message) => Err(message.clone()),
+                    };
+                    BatchResult {
+                        year: challenge.year,
+                        day: challenge.day,
+                        part,
+                        dataset: challenge.dataset_name(*dataset).to_string(),
+                        outcome,
+                        duration: parse_duration + start.elapsed(),
+                    }
+                })
+                .collect()
+        }
+        BatchTask::LoadFailed { key, message } => vec![BatchResult {
+            year: key.year,
+            day: key.day,
+            part: 1,
+            dataset: requested_dataset_name(params),
+            outcome: Err(message.clone()),
+            duration: Duration::ZERO,
+        }],
+    }
+}
+
+/// The dataset named on the command line, for results whose challenge could not be loaded.
+pub fn requested_dataset_name(params: &AppParams) -> String {
+    match &params.example {
+        Some(name) => name.clone(),
+        None if params.use_example_data => String::from(DEFAULT_EXAMPLE_NAME),
+        None => String::from("real"),
+    }
+}
+
+pub fn run_batch_with_summary(
+    source: &dyn ChallengeSource,
+    days: RangeInclusive<u8>,
+    params: &AppParams,
+) -> Result<()> {
+    let results = run_batch(source, solution_registry()?, days, params);
+    match params.output_format {
+        OutputFormat::Text => print_summary(&results),
+        OutputFormat::Json => {
+            let records: Vec<BatchRecord> = results.iter().map(BatchResult::record).collect();
+            println!("{}", serde_json::to_string_pretty(&records)?)
+        }
+        OutputFormat::Tsv => print_tsv(&results),
+    }
+    let failures = results
+        .iter()
+        .filter(|result| result.outcome.is_err())
+        .count();
+    if failures > 0 {
+        Err(Box::new(Error::new(&format!(
+            "{} of {} solutions failed!",
+            failures,
+            results.len()
+        ))))
+    } else {
+        Ok(())
+    }
+}
+
+pub fn print_summary(results: &[BatchResult]) {
+    println!(" Day | Part | Dataset  | Time (ms) | Answer");
+    println!("-----+------+----------+-----------+--------");
+    for result in results {
+        println!(
+            " {:>3} | {:>4} | {:<8} | {:>9.3} | {}",
+            result.day,
+            result.part,
+            result.dataset,
+            result.duration.as_secs_f64() * 1000.0,
+            match &result.outcome {
+                Ok(Answer::MultiLine(_)) => String::from("(multi-line, see below)"),
+                Ok(answer) => answer.to_string(),
+                Err(message) => match message.split_once('\n') {
+                    Some((headline, _)) => format!("error: {} (see below)", headline),
+                    None => format!("error: {}", message),
+                },
+            }
+        );
+    }
+    for result in results {
+        match &result.outcome {
+            Ok(answer @ Answer::MultiLine(_)) => {
+                println!("\nDay {} part {}:{}", result.day, result.part, answer)
+            }
+            Err(message) if message.contains('\n') => {
+                println!(
+                    "\nDay {} part {} error:\n{}",
+                    result.day, result.part, message
+                )
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Prints one tab-separated line per result, with tabs and newlines in answers escaped.
+pub fn print_tsv(results: &[BatchResult]) {
+    println!("year\tday\tpart\tdataset\tanswer\twall_time_ms\terror");
+    for result in results {
+        println!("{}", tsv_row(result));
+    }
+}
+
+fn tsv_row(result: &BatchResult) -> String {
+    let (answer, error) = match &result.outcome {
+        Ok(Answer::MultiLine(text)) => (text.clone(), String::new()),
+        Ok(answer) => (answer.to_string(), String::new()),
+        Err(message) => (String::new(), message.clone()),
+    };
+    format!(
+        "{}\t{}\t{}\t{}\t{}\t{:.3}\t{}",
+        result.year,
+        result.day,
+        result.part,
+        tsv_escape(&result.dataset),
+        tsv_escape(&answer),
+        result.duration.as_secs_f64() * 1000.0,
+        tsv_escape(&error)
+    )
+}
+
+pub fn tsv_escape(field: &str) -> String {
+    field
+        .replace('\\', "\\\\")
+        .replace('\t', "\\t")
+        .replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(outcome: std::result::Result<Answer, String>) -> BatchResult {
+        BatchResult {
+            year: 2021,
+            day: 13,
+            part: 2,
+            dataset: String::from("real"),
+            outcome,
+            duration: Duration::from_micros(1500),
+        }
+    }
+
+    #[test]
+    fn records_have_either_an_answer_or_an_error() -> Result<()> {
+        let answered = serde_json::to_value(result(Ok(Answer::from(42))).record())?;
+        assert_eq!(
+            serde_json::json!({"year": 2021, "day": 13, "part": 2, "dataset": "real", "answer": 42,
+                "wall_time_ms": 1.5, "error": null}),
+            answered
+        );
+        let failed = serde_json::to_value(result(Err(String::from("bad input"))).record())?;
+        assert_eq!(serde_json::Value::Null, failed["answer"]);
+        assert_eq!("bad input", failed["error"]);
+        Ok(())
+    }
+
+    #[test]
+    fn tsv_rows_escape_multi_line_answers() {
+        assert_eq!(
+            "2021\t13\t2\treal\t#..#\\n.##.\t1.500\t",
+            tsv_row(&result(Ok(Answer::from("#..#\n.##."))))
+        );
+    }
+}
@@ -0,0 +1,337 @@
+use std::ops::RangeInclusive;
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+
+use super::batch::{requested_dataset_name, tsv_escape};
+use super::challenge::ChallengeKey;
+use super::input::load_challenge;
+use super::solution::{resolve_dataset, solution_registry, ChallengeSolution};
+use super::source::ChallengeSource;
+use crate::common::{describe_error, OutputFormat};
+use crate::{AppParams, Error, Result};
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct PhaseStats {
+    pub min_ms: f64,
+    pub median_ms: f64,
+    pub mean_ms: f64,
+    pub std_dev_ms: f64,
+}
+
+impl PhaseStats {
+    pub fn from_samples(samples: &[Duration]) -> PhaseStats {
+        let mut millis: Vec<f64> = samples
+            .iter()
+            .map(|sample| sample.as_secs_f64() * 1000.0)
+            .collect();
+        if millis.is_empty() {
+            return PhaseStats {
+                min_ms: 0.0,
+                median_ms: 0.0,
+                mean_ms: 0.0,
+                std_dev_ms: 0.0,
+            };
+        }
+        millis.sort_by(|a, b| a.total_cmp(b));
+        let count = millis.len() as f64;
+        let mean = millis.iter().sum::<f64>() / count;
+        let variance = millis.iter().map(|n| (n - mean) * (n - mean)).sum::<f64>() / count;
+        let middle = millis.len() / 2;
+        let median = if millis.len().is_multiple_of(2) {
+            (millis[middle - 1] + millis[middle]) / 2.0
+        } else {
+            millis[middle]
+        };
+        PhaseStats {
+            min_ms: millis[0],
+            median_ms: median,
+            mean_ms: mean,
+            std_dev_ms: variance.sqrt(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct BenchReport {
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+    pub dataset: String,
+    pub iterations: u32,
+    /// `None` when the day failed, and `error` says why.
+    pub parse: Option<PhaseStats>,
+    pub solve: Option<PhaseStats>,
+    pub error: Option<String>,
+}
+
+/// Benchmarks every part of the selected days that has a solution. A day that cannot be loaded,
+/// parsed or solved gets a report with its error, and the other days are still benchmarked.
+pub fn run_bench(
+    source: &dyn ChallengeSource,
+    days: RangeInclusive<u8>,
+    iterations: u32,
+    params: &AppParams,
+) -> Result<Vec<BenchReport>> {
+    let solutions = solution_registry()?;
+    let mut reports: Vec<BenchReport> = vec![];
+    for day in days {
+        let key = ChallengeKey::new(params.year, day);
+        if solutions.get(key).is_none() {
+            continue;
+        }
+        let report = |part: u8, dataset: &str, outcome: Result<(PhaseStats, PhaseStats)>| {
+            let (stats, error) = match outcome {
+                Ok(stats) => (Some(stats), None),
+                Err(err) => (None, Some(describe_error(err.as_ref()))),
+            };
+            BenchReport {
+                year: key.year,
+                day,
+                part,
+                dataset: dataset.to_string(),
+                iterations,
+                parse: stats.as_ref().map(|(parse, _)| parse.clone()),
+                solve: stats.map(|(_, solve)| solve),
+                error,
+            }
+        };
+        let loaded = load_challenge(source, key, params).and_then(|challenge| {
+            let dataset = resolve_dataset(&challenge, params)?;
+            Ok((challenge, dataset))
+        });
+        let (challenge, dataset) = match loaded {
+            Ok(loaded) => loaded,
+            Err(err) => {
+                let part = params.challenge_part.map_or(1, |part| part as u8);
+                reports.push(report(part, &requested_dataset_name(params), Err(err)));
+                continue;
+            }
+        };
+        for part in 1..=challenge.parts {
+            if params.challenge_part.is_some_and(|p| p as u8 != part) {
+                continue;
+            }
+            let solution = match solutions.get_part(key, part) {
+                Some(solution) => solution,
+                None => continue,
+            };
+            let outcome = bench_part(solution, challenge.data_for(dataset), part, iterations);
+            reports.push(report(part, challenge.dataset_name(dataset), outcome));
+        }
+    }
+    Ok(reports)
+}
+
+/// Times parsing `data` and solving `part` from it, `iterations` times over.
+fn bench_part(
+    solution: ChallengeSolution,
+    data: &str,
+    part: u8,
+    iterations: u32,
+) -> Result<(PhaseStats, PhaseStats)> {
+    let mut parse_samples: Vec<Duration> = vec![];
+    let mut solve_samples: Vec<Duration> = vec![];
+    for _ in 0..iterations {
+        let parse_start = Instant::now();
+        let input = solution.parse(data)?;
+        parse_samples.push(parse_start.elapsed());
+        let solve_start = Instant::now();
+        solution.solve(part, &input)?;
+        solve_samples.push(solve_start.elapsed());
+    }
+    Ok((
+        PhaseStats::from_samples(&parse_samples),
+        PhaseStats::from_samples(&solve_samples),
+    ))
+}
+
+pub fn run_bench_with_report(
+    source: &dyn ChallengeSource,
+    days: RangeInclusive<u8>,
+    iterations: u32,
+    params: &AppParams,
+) -> Result<()> {
+    let reports = run_bench(source, days, iterations, params)?;
+    match params.output_format {
+        OutputFormat::Text => print_bench_reports(&reports),
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&reports)?),
+        OutputFormat::Tsv => print_bench_tsv(&reports),
+    }
+    let failures = reports
+        .iter()
+        .filter(|report| report.error.is_some())
+        .count();
+    if failures > 0 {
+        Err(Box::new(Error::new(&format!(
+            "{} of {} benchmarks failed!",
+            failures,
+            reports.len()
+        ))))
+    } else {
+        Ok(())
+    }
+}
+
+pub fn print_bench_reports(reports: &[BenchReport]) {
+    println!(" Day | Part | Phase |   Min (ms) | Median (ms) |  Mean (ms) | Std dev (ms)");
+    println!("-----+------+-------+------------+-------------+------------+-------------");
+    for report in reports {
+        let phases = match (&report.parse, &report.solve, &report.error) {
+            (Some(parse), Some(solve), None) => [("parse", parse), ("solve", solve)],
+            (_, _, error) => {
+                let message = error.as_deref().unwrap_or_default();
+                println!(
+                    " {:>3} | {:>4} | error: {}",
+                    report.day,
+                    report.part,
+                    match message.split_once('\n') {
+                        Some((headline, _)) => format!("{} (see below)", headline),
+                        None => message.to_string(),
+                    }
+                );
+                continue;
+            }
+        };
+        for (phase, stats) in phases {
+            println!(
+                " {:>3} | {:>4} | {:<5} | {:>10.3} | {:>11.3} | {:>10.3} | {:>12.3}",
+                report.day,
+                report.part,
+                phase,
+                stats.min_ms,
+                stats.median_ms,
+                stats.mean_ms,
+                stats.std_dev_ms
+            );
+        }
+    }
+    for report in reports {
+        if let Some(message) = report
+            .error
+            .as_ref()
+            .filter(|message| message.contains('\n'))
+        {
+            println!(
+                "\nDay {} part {} error:\n{}",
+                report.day, report.part, message
+            );
+        }
+    }
+}
+
+pub fn print_bench_tsv(reports: &[BenchReport]) {
+    println!(
+        "year\tday\tpart\tdataset\tphase\titerations\tmin_ms\tmedian_ms\tmean_ms\tstd_dev_ms\terror"
+    );
+    for report in reports {
+        let phases = match (&report.parse, &report.solve) {
+            (Some(parse), Some(solve)) => vec![("parse", parse), ("solve", solve)],
+            _ => vec![],
+        };
+        for (phase, stats) in phases {
+            println!(
+                "{}\t{}\t{}\t{}\t{}\t{}\t{:.3}\t{:.3}\t{:.3}\t{:.3}\t",
+                report.year,
+                report.day,
+                report.part,
+                report.dataset,
+                phase,
+                report.iterations,
+                stats.min_ms,
+                stats.median_ms,
+                stats.mean_ms,
+                stats.std_dev_ms
+            );
+        }
+        if let Some(error) = &report.error {
+            println!(
+                "{}\t{}\t{}\t{}\t\t{}\t\t\t\t\t{}",
+                report.year,
+                report.day,
+                report.part,
+                report.dataset,
+                report.iterations,
+                tsv_escape(error)
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aoc::challenge::Challenge;
+    use crate::aoc::source::MemorySource;
+    use crate::cli::parse_args;
+    use crate::config::Config;
+
+    #[test]
+    fn failing_days_are_reported_and_the_rest_are_benchmarked() -> Result<()> {
+        let source = MemorySource::new()
+            .with_challenge(Challenge {
+                data: String::from("199\n200\n208"),
+                ..Challenge::new(ChallengeKey::new(2021, 1))
+            })
+            .with_challenge(Challenge {
+                data: String::from("1"),
+                ..Challenge::new(ChallengeKey::new(2021, 9))
+            });
+        let params = parse_args(
+            ["aoc2021", "bench", "--all"].into_iter().map(String::from),
+            &Config::default(),
+        )?;
+        let reports = run_bench(&source, 1..=9, 1, &params)?;
+        let outcomes: Vec<(u8, u8, bool)> = reports
+            .iter()
+            .map(|report| (report.day, report.part, report.error.is_none()))
+            .collect();
+        assert_eq!(
+            vec![
+                (1, 1, true),
+                (1, 2, true),
+                (2, 1, false),
+                (3, 1, false),
+                (4, 1, false),
+                (5, 1, false),
+                (6, 1, false),
+                (7, 1, false),
+                (8, 1, false),
+                (9, 1, true),
+                (9, 2, false)
+            ],
+            outcomes
+        );
+        assert!(reports[1].solve.is_some() && reports[2].solve.is_none());
+        assert!(reports[2].error.as_ref().unwrap().contains("2021 day 2"));
+        Ok(())
+    }
+
+    #[test]
+    fn phase_stats_are_correct_for_odd_sample_count() {
+        let samples: Vec<Duration> = [4, 1, 3].into_iter().map(Duration::from_millis).collect();
+        let stats = PhaseStats::from_samples(&samples);
+        assert_eq!(1.0, stats.min_ms);
+        assert_eq!(3.0, stats.median_ms);
+        assert!((stats.mean_ms - 8.0 / 3.0).abs() < 1e-9);
+        assert!((stats.std_dev_ms - (14.0_f64 / 9.0).sqrt()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn phase_stats_median_averages_middle_samples() {
+        let samples: Vec<Duration> = [2, 8, 4, 6]
+            .into_iter()
+            .map(Duration::from_millis)
+            .collect();
+        let stats = PhaseStats::from_samples(&samples);
+        assert_eq!(5.0, stats.median_ms);
+        assert_eq!(5.0, stats.mean_ms);
+    }
+
+    #[test]
+    fn phase_stats_are_zero_without_samples() {
+        let stats = PhaseStats::from_samples(&[]);
+        assert_eq!(0.0, stats.min_ms);
+        assert_eq!(0.0, stats.std_dev_ms);
+    }
+}
@@ -0,0 +1,470 @@
+use std::io::{self, Read, Write};
+use std::time::Instant;
+
+use super::challenge::{Challenge, ChallengeKey};
+use super::solution::{solution_registry, Dataset, Registry};
+use super::source::{get_challenge, ChallengeSource};
+use super::verify::{run_verification, VerifyStatus};
+use crate::cli::ALL_CHALLENGE_DAYS;
+use crate::common::{describe_error, AppParams};
+use crate::terminal::{
+    self, Key, RawMode, BOLD, CLEAR_SCREEN, DIM, ENTER_ALTERNATE_SCREEN, GREEN,
+    LEAVE_ALTERNATE_SCREEN, RED, RESET, REVERSE, YELLOW,
+};
+use crate::{Error, Result};
+
+const FIRST_DAY: u8 = *ALL_CHALLENGE_DAYS.start() as u8;
+const LAST_DAY: u8 = *ALL_CHALLENGE_DAYS.end() as u8;
+const WEEKDAYS: [&str; 7] = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+/// Used when the terminal's size is unknown.
+const DEFAULT_ROWS: usize = 24;
+
+/// What the calendar shows about each day.
+struct DayStatus {
+    data_len: Option<usize>,
+    examples: usize,
+    solved_parts: Vec<u8>,
+    verified: Option<VerifyStatus>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Action {
+    Select(i32),
+    RunPart(u8),
+    ToggleExample,
+    ShowInput,
+    Rerun,
+    Quit,
+}
+
+fn action_for(key: Key) -> Option<Action> {
+    match key {
+        Key::Left => Some(Action::Select(-1)),
+        Key::Right => Some(Action::Select(1)),
+        Key::Up => Some(Action::Select(-7)),
+        Key::Down => Some(Action::Select(7)),
+        Key::Char(c @ ('1' | '2')) => Some(Action::RunPart(c as u8 - b'0')),
+        Key::Char('e') => Some(Action::ToggleExample),
+        Key::Char('i') => Some(Action::ShowInput),
+        Key::Char('r') | Key::Enter => Some(Action::Rerun),
+        Key::Char('q') | Key::Interrupt => Some(Action::Quit),
+        _ => None,
+    }
+}
+
+/// The weekday of December 1st in `year`, counting from Monday as 0.
+fn first_weekday(year: u16) -> usize {
+    // Sakamoto's method, which counts from Sunday.
+    let year = year as usize;
+    let from_sunday = (year + year / 4 - year / 100 + year / 400 + 4 + 1) % 7;
+    (from_sunday + 6) % 7
+}
+
+struct Calendar<'a> {
+    source: &'a dyn ChallengeSource,
+    solutions: &'a Registry,
+    year: u16,
+    days: Vec<DayStatus>,
+    selected: u8,
+    use_example: bool,
+    color: bool,
+    last_part: Option<u8>,
+    output: Vec<String>,
+}
+
+impl Calendar<'_> {
+    /// Reads what is stored and solved for every day of the year, and verifies the solutions.
+    fn load<'a>(
+        source: &'a dyn ChallengeSource,
+        solutions: &'a Registry,
+        params: &AppParams,
+    ) -> Result<Calendar<'a>> {
+        let year = params.year;
+        let days = FIRST_DAY..=LAST_DAY;
+        let results = run_verification(source, solutions, year, days.clone(), None, params.jobs);
+        let mut statuses = vec![];
+        for day in days {
+            let key = ChallengeKey::new(year, day);
+            let challenge = source.find_challenge(key)?;
+            let solution = solutions.get(key);
+            let parts = challenge.as_ref().map_or(2, |challenge| challenge.parts);
+            let verified = challenge.as_ref().and(
+                results
+                    .iter()
+                    .filter(|result| result.day == day)
+                    .map(|result| result.status)
+                    .reduce(|verified, status| match (verified, status) {
+                        (VerifyStatus::Fail, _) | (_, VerifyStatus::Fail) => VerifyStatus::Fail,
+                        (VerifyStatus::Pass, _) | (_, VerifyStatus::Pass) => VerifyStatus::Pass,
+                        _ => VerifyStatus::Unknown,
+                    }),
+            );
+            statuses.push(DayStatus {
+                data_len: challenge
+                    .as_ref()
+                    .map(|challenge| challenge.data.len())
+                    .filter(|&len| len > 0),
+                examples: challenge.map_or(0, |challenge| challenge.examples.len()),
+                solved_parts: (1..=parts)
+                    .filter(|&part| solution.is_some_and(|solution| solution.has_part(part)))
+                    .collect(),
+                verified,
+            });
+        }
+        Ok(Calendar {
+            source,
+            solutions,
+            year,
+            days: statuses,
+            selected: params.challenge_day.map_or(1, |day| day as u8),
+            use_example: params.use_example_data,
+            color: params.color,
+            last_part: None,
+            output: vec![],
+        })
+    }
+
+    fn key(&self) -> ChallengeKey {
+        ChallengeKey::new(self.year, self.selected)
+    }
+
+    /// Shows `text` in `style`, unless colors are turned off, when it is left plain.
+    fn style(&self, style: &str, text: &str) -> String {
+        if self.color {
+            format!("{}{}{}", style, text, RESET)
+        } else {
+            String::from(text)
+        }
+    }
+
+    /// Carries out `action`, and returns whether the session goes on.
+    fn apply(&mut self, action: Action) -> bool {
+        let output = match action {
+            Action::Select(offset) => {
+                self.selected =
+                    (self.selected as i32 + offset).clamp(FIRST_DAY as i32, LAST_DAY as i32) as u8;
+                return true;
+            }
+            Action::RunPart(part) => {
+                self.last_part = Some(part);
+                self.run_part(part)
+            }
+            Action::Rerun => match self.last_part {
+                Some(part) => self.run_part(part),
+                None => Ok(vec![String::from(
+                    "Run a part first, then r runs it again.",
+                )]),
+            },
+            Action::ToggleExample => {
+                self.use_example = !self.use_example;
+                Ok(vec![format!("Using {} data.", self.data_kind())])
+            }
+            Action::ShowInput => self.show_input(),
+            Action::Quit => return false,
+        };
+        self.output = output.unwrap_or_else(|err| {
+            describe_error(err.as_ref())
+                .lines()
+                .map(String::from)
+                .collect()
+        });
+        true
+    }
+
+    fn data_kind(&self) -> &'static str {
+        if self.use_example {
+            "example"
+        } else {
+            "puzzle"
+        }
+    }
+
+    fn dataset(&self, challenge: &Challenge) -> Result<Dataset> {
+        if !self.use_example {
+            return Ok(Dataset::Real);
+        }
+        if challenge.examples.is_empty() {
+            return Err(Box::new(Error::new(&format!(
+                "{} has no examples!",
+                challenge.key()
+            ))));
+        }
+        Ok(Dataset::Example(0))
+    }
+
+    /// Loads the challenge again, so changes to the stored data show up on every run.
+    fn run_part(&self, part: u8) -> Result<Vec<String>> {
+        let key = self.key();
+        let solution = match self.solutions.get_part(key, part) {
+            Some(solution) => solution,
+            None => {
+                return Err(Box::new(Error::SolutionNotFound {
+                    year: key.year,
+                    day: key.day,
+                    part,
+                }))
+            }
+        };
+        let challenge = get_challenge(self.source, key)?;
+        let dataset = self.dataset(&challenge)?;
+        let started = Instant::now();
+        let answer = solution.run_on(&challenge, dataset, part)?.to_string();
+        let elapsed = started.elapsed();
+        let check = match challenge.expected_answer(dataset, part) {
+            Some(expected) if expected.trim() == answer.trim() => {
+                self.style(GREEN, "matches the recorded answer")
+            }
+            Some(expected) => self.style(RED, &format!("expected {}", expected.trim())),
+            None => String::from("no recorded answer"),
+        };
+        let mut output = vec![format!(
+            "{} part {} on {} data in {:.3} ms, {}:",
+            key,
+            part,
+            challenge.dataset_name(dataset),
+            elapsed.as_secs_f64() * 1000.0,
+            check
+        )];
+        output.extend(answer.trim_end().lines().map(String::from));
+        Ok(output)
+    }
+
+    fn show_input(&self) -> Result<Vec<String>> {
+        let challenge = get_challenge(self.source, self.key())?;
+        let dataset = self.dataset(&challenge)?;
+        let data = challenge.data_for(dataset);
+        let mut output = vec![format!(
+            "{} {} data, {} lines:",
+            challenge.key(),
+            challenge.dataset_name(dataset),
+            data.lines().count()
+        )];
+        output.extend(data.lines().map(String::from));
+        Ok(output)
+    }
+
+    fn day_cell(&self, day: u8) -> String {
+        let status = &self.days[day as usize - 1];
+        let data = match status.data_len {
+            Some(_) => String::from("■"),
+            None => self.style(DIM, "□"),
+        };
+        let stars = self.style(
+            YELLOW,
+            &format!("{:<2}", "*".repeat(status.solved_parts.len())),
+        );
+        let verified = match status.verified {
+            Some(VerifyStatus::Pass) => self.style(GREEN, "✓"),
+            Some(VerifyStatus::Fail) => self.style(RED, "✗"),
+            Some(VerifyStatus::Unknown) => String::from("?"),
+            None => String::from(" "),
+        };
+        let number = format!("{:>2}", day);
+        let (number, marker) = match (day == self.selected, self.color) {
+            (true, true) => (self.style(REVERSE, &number), " "),
+            // Without colors, the selected day is marked after its cell instead.
+            (true, false) => (number, "<"),
+            (false, _) => (number, " "),
+        };
+        format!("{} {}{}{}{}", number, data, stars, verified, marker)
+    }
+
+    fn summary(&self) -> String {
+        let status = &self.days[self.selected as usize - 1];
+        let data = match status.data_len {
+            Some(len) => format!("{} bytes of input", len),
+            None => String::from("no input"),
+        };
+        let solved = match status.solved_parts.as_slice() {
+            [] => String::from("no solutions"),
+            parts => format!(
+                "solutions for part {}",
+                parts
+                    .iter()
+                    .map(u8::to_string)
+                    .collect::<Vec<String>>()
+                    .join(" and ")
+            ),
+        };
+        let verified = match status.verified {
+            Some(verified) => format!("verification: {}", verified),
+            None => String::from("not verified"),
+        };
+        format!(
+            "{}: {}, {} example{}, {}, {}",
+            self.style(BOLD, &self.key().to_string()),
+            data,
+            status.examples,
+            if status.examples == 1 { "" } else { "s" },
+            solved,
+            verified
+        )
+    }
+
+    /// Draws the whole screen, with as much of the output as fits in `rows`.
+    fn render(&self, rows: usize) -> String {
+        let mut lines = vec![
+            format!(
+                "{}   using {} data",
+                self.style(BOLD, &format!("Advent of Code {}", self.year)),
+                self.data_kind()
+            ),
+            String::new(),
+            WEEKDAYS
+                .iter()
+                .map(|weekday| format!(" {:<7}", weekday))
+                .collect(),
+        ];
+        let offset = first_weekday(self.year);
+        let mut week = "        ".repeat(offset);
+        for day in FIRST_DAY..=LAST_DAY {
+            week.push_str(&self.day_cell(day));
+            if (offset + day as usize).is_multiple_of(7) || day == LAST_DAY {
+                lines.push(week);
+                week = String::new();
+            }
+        }
+        lines.push(String::new());
+        lines.push(self.style(
+            DIM,
+            "■ input  * solved part  ✓ verified  ✗ failing  ? no recorded answers",
+        ));
+        lines.push(self.summary());
+        lines.push(self.style(
+            DIM,
+            "arrows select · 1 2 run a part · e example data · i input · r re-run · q quit",
+        ));
+        lines.push(String::new());
+
+        let room = rows.saturating_sub(lines.len() + 1);
+        if self.output.len() > room {
+            let shown = room.saturating_sub(1);
+            lines.extend(self.output[..shown].iter().cloned());
+            lines.push(self.style(DIM, &format!("… {} more lines", self.output.len() - shown)));
+        } else {
+            lines.extend(self.output.iter().cloned());
+        }
+        format!("{}{}", CLEAR_SCREEN, lines.join("\r\n"))
+    }
+}
+
+/// Redraws the calendar after every key until the user quits or the input ends.
+fn run_session(
+    calendar: &mut Calendar,
+    input: &mut dyn Read,
+    output: &mut dyn Write,
+) -> Result<()> {
+    loop {
+        let rows = terminal::terminal_size().map_or(DEFAULT_ROWS, |(rows, _)| rows);
+        write!(output, "{}", calendar.render(rows))?;
+        output.flush()?;
+        let key = match terminal::read_key(input)? {
+            Some(key) => key,
+            None => return Ok(()),
+        };
+        if let Some(action) = action_for(key) {
+            if !calendar.apply(action) {
+                return Ok(());
+            }
+        }
+    }
+}
+
+/// Shows the year as a calendar in the terminal, where days can be picked and run until the user
+/// quits.
+pub fn run_calendar(source: &dyn ChallengeSource, params: &AppParams) -> Result<()> {
+    let raw_mode = RawMode::enable().map_err(|err| {
+        Error::io(
+            "The calendar needs an interactive terminal; try list or run instead!",
+            err,
+        )
+    })?;
+    let mut calendar = Calendar::load(source, solution_registry()?, params)?;
+    let mut stdout = io::stdout();
+    write!(stdout, "{}", ENTER_ALTERNATE_SCREEN)?;
+    let result = run_session(&mut calendar, &mut io::stdin().lock(), &mut stdout);
+    write!(stdout, "{}", LEAVE_ALTERNATE_SCREEN)?;
+    stdout.flush()?;
+    drop(raw_mode);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aoc::challenge::Example;
+    use crate::aoc::source::MemorySource;
+    use crate::cli::parse_args;
+    use crate::config::Config;
+
+    const DEPTHS: &str = "199\n200\n208\n210\n200\n207\n240\n269\n260\n263";
+
+    #[test]
+    fn december_starts_on_the_right_weekday() {
+        assert_eq!(1, first_weekday(2015));
+        assert_eq!(2, first_weekday(2021));
+        assert_eq!(3, first_weekday(2022));
+        assert_eq!(0, first_weekday(2025));
+    }
+
+    #[test]
+    fn keys_select_days_and_run_parts_in_one_session() -> Result<()> {
+        let source = MemorySource::new().with_challenge(Challenge {
+            examples: vec![Example::new("example", String::from("1\n2\n3\n4\n5"))],
+            data: String::from(DEPTHS),
+            ..Challenge::new(ChallengeKey::new(2021, 1))
+        });
+        let params = parse_args(
+            ["aoc2021", "calendar"].into_iter().map(String::from),
+            &Config::default(),
+        )?;
+        let mut calendar = Calendar::load(&source, solution_registry()?, &params)?;
+        assert_eq!(Some(VerifyStatus::Unknown), calendar.days[0].verified);
+        assert_eq!(None, calendar.days[1].verified);
+        assert_eq!(vec![1, 2], calendar.days[1].solved_parts);
+
+        let mut input: &[u8] = b"\x1b[D\x1b[B\x1b[A2";
+        let mut screen: Vec<u8> = vec![];
+        run_session(&mut calendar, &mut input, &mut screen)?;
+        assert_eq!(1, calendar.selected);
+        assert_eq!(Some("5"), calendar.output.last().map(String::as_str));
+        assert!(String::from_utf8_lossy(&screen).contains("Advent of Code 2021"));
+
+        calendar.apply(Action::ToggleExample);
+        calendar.apply(Action::Rerun);
+        assert_eq!(Some("2"), calendar.output.last().map(String::as_str));
+        calendar.apply(Action::Select(1));
+        calendar.apply(Action::ShowInput);
+        assert_eq!(
+            vec!["Challenge 2021 day 2 not found in memory!"],
+            calendar.output
+        );
+        assert!(!calendar.apply(action_for(Key::Char('q')).unwrap()));
+        Ok(())
+    }
+
+    #[test]
+    fn only_screen_control_codes_are_sent_with_colors_off() -> Result<()> {
+        let source = MemorySource::new().with_challenge(Challenge {
+            data: String::from(DEPTHS),
+            ..Challenge::new(ChallengeKey::new(2021, 1))
+        });
+        let params = parse_args(
+            ["aoc2021", "calendar", "--color", "off"]
+                .into_iter()
+                .map(String::from),
+            &Config::default(),
+        )?;
+        let mut calendar = Calendar::load(&source, solution_registry()?, &params)?;
+        calendar.apply(Action::RunPart(1));
+        let screen = calendar.render(40);
+        let body = screen.strip_prefix(CLEAR_SCREEN).unwrap();
+        assert!(!body.contains("\x1b["), "{:?}", body);
+        assert!(body.contains(" 1 ■**?<"));
+        assert!(body.contains("no recorded answer"));
+
+        calendar.color = true;
+        assert!(calendar.render(40).matches("\x1b[").count() > 10);
+        Ok(())
+    }
+}
@@ -1,21 +1,177 @@
-use std::collections::HashMap;
-use std::fs::File;
-use std::io::Read;
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
 
 use serde::{Deserialize, Serialize};
 
+use super::solution::Dataset;
+#[cfg(test)]
 use crate::Result;
 
-#[derive(Deserialize, Serialize)]
+pub const DEFAULT_EXAMPLE_NAME: &str = "example";
+
+/// Identifies a challenge: the day of an event, and the year of the event.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ChallengeKey {
+    pub year: u16,
+    pub day: u8,
+}
+
+impl ChallengeKey {
+    pub fn new(year: u16, day: u8) -> ChallengeKey {
+        ChallengeKey { year, day }
+    }
+}
+
+impl Display for ChallengeKey {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} day {}", self.year, self.day)
+    }
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct Example {
+    pub name: String,
+    #[serde(default)]
+    pub answers: BTreeMap<u8, String>,
+    pub data: String,
+}
+
+impl Example {
+    pub fn new(name: &str, data: String) -> Example {
+        Example {
+            name: String::from(name),
+            answers: BTreeMap::new(),
+            data,
+        }
+    }
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+#[serde(from = "ChallengeRecord")]
 pub struct Challenge {
-    #[serde(default = "default_challenge_day")]
+    pub year: u16,
     pub day: u8,
-    #[serde(default = "default_challenge_parts")]
     pub parts: u8,
-    pub example_data: String,
+    pub examples: Vec<Example>,
+    pub answers: BTreeMap<u8, String>,
     pub data: String,
 }
 
+/// The stored form of a challenge, which also accepts the single `example_data` and
+/// `example_answers` fields used before challenges could have several named examples.
+#[derive(Deserialize)]
+struct ChallengeRecord {
+    #[serde(default)]
+    year: u16,
+    #[serde(default = "default_challenge_day")]
+    day: u8,
+    #[serde(default = "default_challenge_parts")]
+    parts: u8,
+    #[serde(default)]
+    examples: Vec<Example>,
+    #[serde(default)]
+    example_answers: BTreeMap<u8, String>,
+    example_data: Option<String>,
+    #[serde(default)]
+    answers: BTreeMap<u8, String>,
+    #[serde(default)]
+    data: String,
+}
+
+impl From<ChallengeRecord> for Challenge {
+    fn from(record: ChallengeRecord) -> Self {
+        let mut examples = record.examples;
+        if let Some(example_data) = record.example_data {
+            examples.insert(
+                0,
+                Example {
+                    answers: record.example_answers,
+                    ..Example::new(DEFAULT_EXAMPLE_NAME, example_data)
+                },
+            );
+        }
+        Challenge {
+            year: record.year,
+            day: record.day,
+            parts: record.parts,
+            examples,
+            answers: record.answers,
+            data: record.data,
+        }
+    }
+}
+
+impl Challenge {
+    pub fn new(key: ChallengeKey) -> Challenge {
+        Challenge {
+            year: key.year,
+            day: key.day,
+            parts: 2,
+            examples: vec![],
+            answers: BTreeMap::new(),
+            data: String::new(),
+        }
+    }
+
+    pub fn key(&self) -> ChallengeKey {
+        ChallengeKey::new(self.year, self.day)
+    }
+
+    pub fn example_index(&self, name: &str) -> Option<usize> {
+        self.examples
+            .iter()
+            .position(|example| example.name == name)
+    }
+
+    /// Every dataset this challenge can be run against: each example, then the puzzle data.
+    pub fn datasets(&self) -> Vec<Dataset> {
+        let mut datasets: Vec<Dataset> = (0..self.examples.len()).map(Dataset::Example).collect();
+        if !self.data.is_empty() {
+            datasets.push(Dataset::Real);
+        }
+        datasets
+    }
+
+    pub fn dataset_name(&self, dataset: Dataset) -> &str {
+        match dataset {
+            Dataset::Example(index) => self
+                .examples
+                .get(index)
+                .map_or(DEFAULT_EXAMPLE_NAME, |example| example.name.as_str()),
+            Dataset::Real => "real",
+        }
+    }
+
+    pub fn expected_answer(&self, dataset: Dataset, part: u8) -> Option<&str> {
+        let answers = match dataset {
+            Dataset::Example(index) => &self.examples.get(index)?.answers,
+            Dataset::Real => &self.answers,
+        };
+        answers.get(&part).map(|answer| answer.as_str())
+    }
+
+    pub fn data_for(&self, dataset: Dataset) -> &str {
+        match dataset {
+            Dataset::Example(index) => self
+                .examples
+                .get(index)
+                .map_or("", |example| example.data.as_str()),
+            Dataset::Real => &self.data,
+        }
+    }
+
+    /// Replaces the data for `dataset`, adding an example if there is none at that index yet.
+    pub fn set_data_for(&mut self, dataset: Dataset, data: String) {
+        match dataset {
+            Dataset::Example(index) => match self.examples.get_mut(index) {
+                Some(example) => example.data = data,
+                None => self.examples.push(Example::new(DEFAULT_EXAMPLE_NAME, data)),
+            },
+            Dataset::Real => self.data = data,
+        }
+    }
+}
+
 fn default_challenge_day() -> u8 {
     0
 }
@@ -24,24 +180,43 @@ fn default_challenge_parts() -> u8 {
     1
 }
 
-pub fn get_challenge(day: u8) -> Result<Challenge> {
-    let mut challenges_str = String::new();
-    let _result = File::open("./data/challenges.json")?.read_to_string(&mut challenges_str);
-    let challenges_map: HashMap<String, Challenge> = serde_json::from_str(challenges_str.as_str())?;
-    match challenges_map.get(&format!("{}", day)) {
-        Some(challenge) => Ok(Challenge {
-            day: if challenge.day == 0 {
-                day
-            } else {
-                challenge.day
-            },
-            parts: challenge.parts,
-            example_data: challenge.example_data.clone(),
-            data: challenge.data.clone(),
-        }),
-        None => Err(Box::new(crate::Error::new(&format!(
-            "Challenge #{} not found in challenges.json!",
-            day
-        )))),
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn expected_answers_are_read_per_dataset_and_part() -> Result<()> {
+        let challenge: Challenge = serde_json::from_str(
+            r#"{"parts": 2, "examples": [{"name": "small", "answers": {"1": "7"}, "data": ""}], "answers": {"2": "1683"}, "data": ""}"#,
+        )?;
+        assert_eq!(Some("7"), challenge.expected_answer(Dataset::Example(0), 1));
+        assert_eq!(None, challenge.expected_answer(Dataset::Example(0), 2));
+        assert_eq!(None, challenge.expected_answer(Dataset::Real, 1));
+        assert_eq!(Some("1683"), challenge.expected_answer(Dataset::Real, 2));
+        Ok(())
+    }
+
+    #[test]
+    fn expected_answers_are_optional() -> Result<()> {
+        let challenge: Challenge =
+            serde_json::from_str(r#"{"examples": [{"name": "small", "data": ""}], "data": ""}"#)?;
+        assert_eq!(None, challenge.expected_answer(Dataset::Example(0), 1));
+        Ok(())
+    }
+
+    #[test]
+    fn single_example_fields_are_read_as_the_first_example() -> Result<()> {
+        let challenge: Challenge = serde_json::from_str(
+            r#"{"example_answers": {"1": "7"}, "example_data": "199", "examples": [{"name": "other", "data": "200"}], "data": ""}"#,
+        )?;
+        assert_eq!(Some(0), challenge.example_index("example"));
+        assert_eq!(Some(1), challenge.example_index("other"));
+        assert_eq!("199", challenge.data_for(Dataset::Example(0)));
+        assert_eq!(Some("7"), challenge.expected_answer(Dataset::Example(0), 1));
+        assert_eq!(
+            vec![Dataset::Example(0), Dataset::Example(1)],
+            challenge.datasets()
+        );
+        Ok(())
     }
 }
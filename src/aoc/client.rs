@@ -0,0 +1,99 @@
+use std::env;
+
+use super::challenge::ChallengeKey;
+use crate::common::ServerParams;
+use crate::{Error, Result};
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+pub const BASE_URL_ENV_VAR: &str = "AOC2021_BASE_URL";
+pub const SESSION_ENV_VAR: &str = "AOC_SESSION";
+const USER_AGENT: &str = "aoc2021 client";
+
+/// Talks to the puzzle server on behalf of the user identified by `session`.
+pub struct AocClient {
+    agent: ureq::Agent,
+    base_url: String,
+    session: String,
+}
+
+impl AocClient {
+    pub fn new(base_url: &str, session: &str) -> AocClient {
+        AocClient {
+            agent: ureq::AgentBuilder::new().user_agent(USER_AGENT).build(),
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.to_string(),
+        }
+    }
+
+    /// Builds a client from `--base-url` and `--session`, falling back to their
+    /// environment variables.
+    pub fn from_params(server: &ServerParams) -> Result<AocClient> {
+        let base_url = match &server.base_url {
+            Some(base_url) => base_url.clone(),
+            None => env::var(BASE_URL_ENV_VAR).unwrap_or_else(|_| String::from(DEFAULT_BASE_URL)),
+        };
+        let session = match &server.session {
+            Some(session) => session.clone(),
+            None => match env::var(SESSION_ENV_VAR).ok().filter(|s| !s.is_empty()) {
+                Some(session) => session,
+                None => {
+                    return Err(Box::new(Error::new(&format!(
+                        "A session token is required: pass --session or set {}!",
+                        SESSION_ENV_VAR
+                    ))))
+                }
+            },
+        };
+        Ok(AocClient::new(&base_url, &session))
+    }
+
+    pub fn input_url(&self, key: ChallengeKey) -> String {
+        format!("{}/{}/day/{}/input", self.base_url, key.year, key.day)
+    }
+
+    pub fn answer_url(&self, key: ChallengeKey) -> String {
+        format!("{}/{}/day/{}/answer", self.base_url, key.year, key.day)
+    }
+
+    pub fn fetch_input(&self, key: ChallengeKey) -> Result<String> {
+        let url = self.input_url(key);
+        let data = read_response(
+            &url,
+            self.agent.get(&url).set("Cookie", &self.cookie()).call(),
+        )?;
+        Ok(data.strip_suffix('\n').unwrap_or(&data).to_string())
+    }
+
+    /// Posts an answer and returns the server's response page.
+    pub fn submit_answer(&self, key: ChallengeKey, part: u8, answer: &str) -> Result<String> {
+        let url = self.answer_url(key);
+        let level = part.to_string();
+        let response = self
+            .agent
+            .post(&url)
+            .set("Cookie", &self.cookie())
+            .send_form(&[("level", &level), ("answer", answer)]);
+        read_response(&url, response)
+    }
+
+    fn cookie(&self) -> String {
+        format!("session={}", self.session)
+    }
+}
+
+fn read_response(
+    url: &str,
+    response: std::result::Result<ureq::Response, ureq::Error>,
+) -> Result<String> {
+    match response {
+        Ok(response) => Ok(response.into_string()?),
+        Err(ureq::Error::Status(status, response)) => Err(Box::new(Error::io(
+            &format!("Request to {} failed with status {}!", url, status),
+            response.status_text().to_string(),
+        ))),
+        Err(err) => Err(Box::new(Error::io(
+            &format!("Request to {} failed!", url),
+            err,
+        ))),
+    }
+}
@@ -0,0 +1,93 @@
+use super::challenge::ChallengeKey;
+use super::client::AocClient;
+use super::source::ChallengeSource;
+use crate::common::FetchParams;
+use crate::Result;
+
+/// Returns the puzzle data for `key`, downloading and saving it only if the store has none.
+/// The second value is whether the data was downloaded.
+pub fn fetch_day_input(
+    source: &mut dyn ChallengeSource,
+    client: &AocClient,
+    key: ChallengeKey,
+) -> Result<(String, bool)> {
+    if let Some(challenge) = source.find_challenge(key)? {
+        if !challenge.data.is_empty() {
+            return Ok((challenge.data, false));
+        }
+    }
+    let data = client.fetch_input(key)?;
+    source.save_data(key, data.clone())?;
+    Ok((data, true))
+}
+
+pub fn run_fetch(source: &mut dyn ChallengeSource, year: u16, fetch: &FetchParams) -> Result<()> {
+    let client = AocClient::from_params(&fetch.server)?;
+    let key = ChallengeKey::new(year, fetch.day as u8);
+    let (data, fetched) = fetch_day_input(source, &client, key)?;
+    if fetched {
+        println!("Fetched the input for {} ({} bytes).", key, data.len());
+    } else {
+        println!(
+            "The input for {} is already stored, so it was not fetched again.",
+            key
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::Ordering;
+
+    use super::*;
+    use crate::aoc::source::MemorySource;
+    use crate::aoc::test_server;
+
+    fn input_server() -> (String, std::sync::Arc<std::sync::atomic::AtomicUsize>) {
+        test_server::start(|request| {
+            let authorized = request.cookie.as_deref() == Some("session=secret");
+            if request.path == "/2022/day/13/input" && authorized {
+                (200, String::from("6,10\n0,14\n"))
+            } else {
+                (400, String::new())
+            }
+        })
+    }
+
+    #[test]
+    fn inputs_are_fetched_once_and_saved() -> Result<()> {
+        let (base_url, requests) = input_server();
+        let client = AocClient::new(&base_url, "secret");
+        let mut source = MemorySource::new();
+        let key = ChallengeKey::new(2022, 13);
+
+        let (data, fetched) = fetch_day_input(&mut source, &client, key)?;
+        assert!(fetched);
+        assert_eq!("6,10\n0,14", data);
+        assert_eq!("6,10\n0,14", source.find_challenge(key)?.unwrap().data);
+
+        let (data, fetched) = fetch_day_input(&mut source, &client, key)?;
+        assert!(!fetched);
+        assert_eq!("6,10\n0,14", data);
+        assert_eq!(1, requests.load(Ordering::SeqCst));
+        Ok(())
+    }
+
+    #[test]
+    fn rejected_requests_are_reported() {
+        let (base_url, _) = input_server();
+        let client = AocClient::new(&base_url, "wrong");
+        let err = client.fetch_input(ChallengeKey::new(2022, 13)).unwrap_err();
+        assert!(err.to_string().ends_with("failed with status 400!"));
+    }
+
+    #[test]
+    fn base_url_trailing_slashes_are_ignored() {
+        let client = AocClient::new("http://localhost:8080/", "secret");
+        assert_eq!(
+            "http://localhost:8080/2021/day/5/input",
+            client.input_url(ChallengeKey::new(2021, 5))
+        );
+    }
+}
@@ -0,0 +1,275 @@
+use std::fs;
+use std::path::Path;
+
+use serde_json::{Map, Value};
+
+use super::challenge::{Challenge, ChallengeKey, Example};
+use super::source::challenges_file_path;
+use crate::cli::{ALL_CHALLENGE_DAYS, DEFAULT_YEAR};
+use crate::common::ImportParams;
+use crate::{AppParams, Error, Result};
+
+/// The puzzle data read from the files named on the command line.
+pub struct ImportedChallenge {
+    pub key: ChallengeKey,
+    pub parts: Option<u8>,
+    pub examples: Vec<Example>,
+    pub data: Option<String>,
+}
+
+impl ImportedChallenge {
+    pub fn read(key: ChallengeKey, import: &ImportParams) -> Result<ImportedChallenge> {
+        let mut examples: Vec<Example> = vec![];
+        for (name, path) in &import.examples {
+            if examples.iter().any(|example| example.name == *name) {
+                return Err(Box::new(Error::new(&format!(
+                    "Example {} was given more than once!",
+                    name
+                ))));
+            }
+            examples.push(Example::new(name, fs::read_to_string(path)?));
+        }
+        Ok(ImportedChallenge {
+            key,
+            parts: import.parts,
+            examples,
+            data: match &import.data {
+                Some(path) => Some(fs::read_to_string(path)?),
+                None => None,
+            },
+        })
+    }
+}
+
+pub fn run_import(params: &AppParams, import: &ImportParams) -> Result<()> {
+    let path = challenges_file_path(params);
+    if path.is_dir() {
+        return Err(Box::new(Error::new(&format!(
+            "Cannot import into {}: only challenges.json files can be updated!",
+            path.display()
+        ))));
+    }
+    let key = ChallengeKey::new(params.year, import.day);
+    let imported = ImportedChallenge::read(key, import)?;
+    let mut challenges: Map<String, Value> = if path.exists() {
+        nest_by_year(serde_json::from_str(&fs::read_to_string(&path)?)?)
+    } else {
+        Map::new()
+    };
+    let inserted = import_challenge(&mut challenges, imported, import.force)?;
+    write_challenges(&path, &challenges)?;
+    println!(
+        "{} challenge {} in {}.",
+        if inserted { "Added" } else { "Updated" },
+        key,
+        path.display()
+    );
+    Ok(())
+}
+
+/// Stored challenges are keyed by year, then by day. Files written before challenges had years
+/// are keyed by day alone, so their challenges are moved under the default year.
+pub fn nest_by_year(challenges: Map<String, Value>) -> Map<String, Value> {
+    let keyed_by_day = !challenges.is_empty()
+        && challenges.keys().all(|key| {
+            key.parse::<u32>()
+                .is_ok_and(|day| ALL_CHALLENGE_DAYS.contains(&day))
+        });
+    if keyed_by_day {
+        Map::from_iter([(DEFAULT_YEAR.to_string(), Value::Object(challenges))])
+    } else {
+        challenges
+    }
+}
+
+/// Inserts or updates the entry for the imported challenge, leaving every other entry as it was.
+/// A placeholder left by `new` is filled in without `force`. Returns whether a new entry was
+/// added.
+pub fn import_challenge(
+    challenges: &mut Map<String, Value>,
+    imported: ImportedChallenge,
+    force: bool,
+) -> Result<bool> {
+    let year = match challenges
+        .entry(imported.key.year.to_string())
+        .or_insert_with(|| Value::Object(Map::new()))
+    {
+        Value::Object(year) => year,
+        _ => {
+            return Err(Box::new(Error::new(&format!(
+                "The challenges for {} are not a JSON object!",
+                imported.key.year
+            ))))
+        }
+    };
+    let day = imported.key.day.to_string();
+    let (mut entry, inserted) = match year.get(&day) {
+        Some(Value::Object(entry)) if is_placeholder(entry) => (entry.clone(), true),
+        Some(_) if !force => {
+            return Err(Box::new(Error::new(&format!(
+                "Challenge {} already exists! Use --force to overwrite it.",
+                imported.key
+            ))))
+        }
+        Some(Value::Object(entry)) => (entry.clone(), false),
+        Some(_) => {
+            return Err(Box::new(Error::new(&format!(
+                "Challenge {} is not a JSON object!",
+                imported.key
+            ))))
+        }
+        None => (Map::new(), true),
+    };
+
+    let mut examples = if inserted {
+        vec![]
+    } else {
+        serde_json::from_value::<Challenge>(Value::Object(entry.clone()))?.examples
+    };
+    for example in imported.examples {
+        match examples
+            .iter_mut()
+            .find(|existing| existing.name == example.name)
+        {
+            Some(existing) => existing.data = example.data,
+            None => examples.push(example),
+        }
+    }
+
+    if imported.parts.is_some() || !entry.contains_key("parts") {
+        let parts = imported.parts.unwrap_or(Challenge::new(imported.key).parts);
+        entry.insert(String::from("parts"), Value::from(parts));
+    }
+    entry.remove("example_data");
+    entry.remove("example_answers");
+    entry.insert(String::from("examples"), serde_json::to_value(&examples)?);
+    if inserted || imported.data.is_some() {
+        entry.insert(
+            String::from("data"),
+            Value::from(imported.data.unwrap_or_default()),
+        );
+    }
+    year.insert(day, Value::Object(entry));
+    Ok(inserted)
+}
+
+/// Whether a stored entry has neither example data nor puzzle input, like the ones `new` adds.
+fn is_placeholder(entry: &Map<String, Value>) -> bool {
+    let is_empty = |field: &str| match entry.get(field) {
+        None | Some(Value::Null) => true,
+        Some(Value::String(text)) => text.is_empty(),
+        Some(Value::Array(items)) => items.is_empty(),
+        Some(_) => false,
+    };
+    is_empty("data") && is_empty("examples") && is_empty("example_data")
+}
+
+pub fn write_challenges(path: &Path, challenges: &Map<String, Value>) -> Result<()> {
+    // Write beside the original and rename over it, so a failed write cannot truncate it.
+    let temp_path = path.with_extension("json.tmp");
+    fs::write(&temp_path, serde_json::to_string_pretty(challenges)?)?;
+    fs::rename(&temp_path, path)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn challenges() -> Map<String, Value> {
+        serde_json::from_str(
+            r#"{"2021": {"2": {"parts": 2, "answers": {"1": "150"}, "example_data": "forward 5", "data": "up 3"}, "1": {"data": "199"}}}"#,
+        )
+        .unwrap()
+    }
+
+    fn imported(year: u16, day: u8) -> ImportedChallenge {
+        ImportedChallenge {
+            key: ChallengeKey::new(year, day),
+            parts: None,
+            examples: vec![Example::new("example", String::from("1,2"))],
+            data: Some(String::from("3,4")),
+        }
+    }
+
+    #[test]
+    fn new_days_are_appended_after_existing_days() -> Result<()> {
+        let mut challenges = challenges();
+        assert!(import_challenge(
+            &mut challenges,
+            imported(2021, 13),
+            false
+        )?);
+        let keys: Vec<&str> = challenges["2021"]
+            .as_object()
+            .unwrap()
+            .keys()
+            .map(|key| key.as_str())
+            .collect();
+        assert_eq!(vec!["2", "1", "13"], keys);
+        let challenge: Challenge = serde_json::from_value(challenges["2021"]["13"].clone())?;
+        assert_eq!(2, challenge.parts);
+        assert_eq!("1,2", challenge.examples[0].data);
+        assert_eq!("3,4", challenge.data);
+        Ok(())
+    }
+
+    #[test]
+    fn new_years_are_added_beside_existing_years() -> Result<()> {
+        let mut challenges = challenges();
+        assert!(import_challenge(&mut challenges, imported(2022, 2), false)?);
+        let keys: Vec<&str> = challenges.keys().map(|key| key.as_str()).collect();
+        assert_eq!(vec!["2021", "2022"], keys);
+        assert_eq!("3,4", challenges["2022"]["2"]["data"]);
+        assert_eq!("up 3", challenges["2021"]["2"]["data"]);
+        Ok(())
+    }
+
+    #[test]
+    fn files_keyed_by_day_hold_the_default_year() {
+        let legacy = challenges()["2021"].as_object().unwrap().clone();
+        assert_eq!(challenges(), nest_by_year(legacy));
+        assert_eq!(challenges(), nest_by_year(challenges()));
+    }
+
+    #[test]
+    fn existing_days_are_not_overwritten_without_force() {
+        let mut challenges = challenges();
+        assert!(import_challenge(&mut challenges, imported(2021, 2), false).is_err());
+        assert_eq!(self::challenges(), challenges);
+    }
+
+    #[test]
+    fn placeholders_are_filled_in_without_force() -> Result<()> {
+        let mut challenges = challenges();
+        let placeholder = ImportedChallenge {
+            key: ChallengeKey::new(2021, 13),
+            parts: Some(1),
+            examples: vec![],
+            data: None,
+        };
+        assert!(import_challenge(&mut challenges, placeholder, false)?);
+        assert!(import_challenge(
+            &mut challenges,
+            imported(2021, 13),
+            false
+        )?);
+        let challenge: Challenge = serde_json::from_value(challenges["2021"]["13"].clone())?;
+        assert_eq!((1, "3,4"), (challenge.parts, challenge.data.as_str()));
+        assert!(import_challenge(&mut challenges, imported(2021, 13), false).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn forced_imports_keep_existing_answers() -> Result<()> {
+        let mut challenges = challenges();
+        assert!(!import_challenge(&mut challenges, imported(2021, 2), true)?);
+        let challenge: Challenge = serde_json::from_value(challenges["2021"]["2"].clone())?;
+        assert_eq!(Some(&String::from("150")), challenge.answers.get(&1));
+        assert_eq!(1, challenge.examples.len());
+        assert_eq!("1,2", challenge.examples[0].data);
+        assert_eq!("3,4", challenge.data);
+        assert_eq!(None, challenges["2021"]["2"].get("example_data"));
+        Ok(())
+    }
+}
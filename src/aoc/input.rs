@@ -0,0 +1,148 @@
+use std::fs;
+use std::io::Read;
+
+use super::challenge::{Challenge, ChallengeKey};
+use super::solution::{resolve_dataset, Dataset};
+use super::source::{get_challenge, ChallengeSource};
+use crate::common::InputSource;
+use crate::{AppParams, Error, Result};
+
+/// Reads the input given with `--input`. Empty input is an error rather than data to run on, so
+/// it never stands in for the dataset it replaces.
+pub fn read_input(source: &InputSource) -> Result<String> {
+    let mut data = String::new();
+    let name = match source {
+        InputSource::Stdin => {
+            if let Err(err) = std::io::stdin().read_to_string(&mut data) {
+                return Err(Box::new(Error::io("Could not read input from stdin!", err)));
+            }
+            String::from("stdin")
+        }
+        InputSource::File(path) => {
+            if let Err(err) =
+                fs::File::open(path).and_then(|mut file| file.read_to_string(&mut data))
+            {
+                return Err(Box::new(Error::io(
+                    &format!("Could not read input from {}!", path.display()),
+                    err,
+                )));
+            }
+            path.display().to_string()
+        }
+    };
+    if data.trim().is_empty() {
+        return Err(Box::new(Error::invalid_data(&format!(
+            "The input from {} is empty!",
+            name
+        ))));
+    }
+    Ok(data)
+}
+
+/// Loads the challenge for `key`, replacing the data for the selected dataset
+/// with the contents of `--input` when one was given.
+pub fn load_challenge(
+    source: &dyn ChallengeSource,
+    key: ChallengeKey,
+    params: &AppParams,
+) -> Result<Challenge> {
+    let input_source = match &params.input {
+        Some(input_source) => input_source,
+        None => return get_challenge(source, key),
+    };
+    let mut challenge = match get_challenge(source, key) {
+        Ok(challenge) => challenge,
+        // The input stands in for the challenge's data, so it needn't be stored, but a store that
+        // can't be read is still an error.
+        Err(err) if matches!(err.downcast_ref(), Some(Error::ChallengeNotFound { .. })) => {
+            Challenge::new(key)
+        }
+        Err(err) => return Err(err),
+    };
+    let data = read_input(input_source)?;
+    // The input may be the only example there is, so only a named one has to be stored already.
+    let dataset = if params.example.is_some() {
+        resolve_dataset(&challenge, params)?
+    } else if params.use_example_data {
+        Dataset::Example(0)
+    } else {
+        Dataset::Real
+    };
+    challenge.set_data_for(dataset, data);
+    Ok(challenge)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::*;
+    use crate::aoc::challenge::Example;
+    use crate::aoc::source::MemorySource;
+    use crate::aoc::test_dir::TestDir;
+    use crate::cli::parse_args;
+    use crate::config::Config;
+
+    /// A store that can never be read, like a corrupt challenges file.
+    struct BrokenSource;
+
+    impl ChallengeSource for BrokenSource {
+        fn find_challenge(&self, _key: ChallengeKey) -> Result<Option<Challenge>> {
+            Err(Box::new(Error::new("The store is corrupt!")))
+        }
+
+        fn location(&self) -> String {
+            String::from("nowhere")
+        }
+
+        fn save_data(&mut self, _key: ChallengeKey, _data: String) -> Result<()> {
+            Err(Box::new(Error::new("The store is corrupt!")))
+        }
+    }
+
+    fn input_params(path: &Path) -> Result<AppParams> {
+        parse_args(
+            ["aoc2021", "run", "--day", "1", "--input"]
+                .into_iter()
+                .map(String::from)
+                .chain([path.display().to_string()]),
+            &Config::default(),
+        )
+    }
+
+    #[test]
+    fn only_a_missing_challenge_is_replaced_by_the_input() -> Result<()> {
+        let dir = TestDir::new("load_challenge_input");
+        let path = dir.join("input.txt");
+        fs::write(&path, "1\n2\n3")?;
+        let params = input_params(&path)?;
+        let key = ChallengeKey::new(2021, 1);
+        let challenge = load_challenge(&MemorySource::new(), key, &params)?;
+        assert_eq!("1\n2\n3", challenge.data);
+        match load_challenge(&BrokenSource, key, &params) {
+            Err(err) => assert_eq!("The store is corrupt!", err.to_string()),
+            Ok(_) => panic!("A corrupt store was ignored"),
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn empty_input_is_not_replaced_by_an_example() -> Result<()> {
+        let dir = TestDir::new("load_empty_input");
+        let path = dir.join("empty.txt");
+        fs::write(&path, "\n\n")?;
+        let key = ChallengeKey::new(2021, 1);
+        let source = MemorySource::new().with_challenge(Challenge {
+            examples: vec![Example::new("example", String::from("199\n200"))],
+            ..Challenge::new(key)
+        });
+        match load_challenge(&source, key, &input_params(&path)?) {
+            Err(err) => assert_eq!(
+                format!("The input from {} is empty!", path.display()),
+                err.to_string()
+            ),
+            Ok(_) => panic!("Empty input was replaced by the example"),
+        }
+        Ok(())
+    }
+}
@@ -1,13 +1,22 @@
-use crate::{prompt, Result};
+use crate::prompt::{self, Prompter};
+use crate::Result;
 
-pub fn prompt_select_challenge_day() -> Result<u8> {
-    return prompt::prompt_select_bounded_number("Please select a challenge. (1-25)", 1, 25);
+pub fn prompt_select_challenge_day(prompter: &mut dyn Prompter, year: u16) -> Result<u8> {
+    let day = prompt::prompt_select_bounded_number(
+        prompter,
+        &format!("Please select a challenge from {}. (1-25)", year),
+        1,
+        25,
+    )?;
+    Ok(day as u8)
 }
 
-pub fn prompt_select_challenge_part(num_parts: u8) -> Result<u8> {
-    return prompt::prompt_select_bounded_number(
+pub fn prompt_select_challenge_part(prompter: &mut dyn Prompter, num_parts: u8) -> Result<u8> {
+    let part = prompt::prompt_select_bounded_number(
+        prompter,
         &format!("Which challenge part? (1-{})", num_parts),
         1,
-        num_parts,
-    );
+        num_parts as usize,
+    )?;
+    Ok(part as u8)
 }
@@ -0,0 +1,411 @@
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde_json::{Map, Value};
+
+use super::challenge::ChallengeKey;
+use super::import::{import_challenge, nest_by_year, write_challenges, ImportedChallenge};
+use super::source::{challenges_file_path, ChallengeSource, DirectorySource};
+use crate::common::NewParams;
+use crate::{AppParams, Error, Result};
+
+/// Where `new` writes the code, relative to the root of the repository.
+const SOURCE_DIR: &str = "./src";
+/// The IDE's file template for a day, with `${DAY}` where the day goes.
+const DAY_TEMPLATE: &str = include_str!("../../.idea/fileTemplates/Advent of Code Day Solution.rs");
+/// A sub-module with a stub for the day's solution to call, and an example test that is ignored
+/// until the example and its answer are filled in.
+const SUB_MODULE_TEMPLATE: &str = "\
+use crate::common;
+
+/// Works out the puzzle's answer from its input. Call it from the day's solution.
+pub fn solve(input: &str) -> common::Result<usize> {
+    Ok(input.lines().count())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Paste the puzzle's example here.
+    const TEST_STR: &str = \"\";
+
+    #[test]
+    #[ignore = \"fill in TEST_STR and the example's answer\"]
+    fn solve_gets_example_correct() {
+        assert_eq!(0, solve(TEST_STR).unwrap());
+    }
+}
+";
+
+pub fn run_new(params: &AppParams, new: &NewParams) -> Result<()> {
+    let key = ChallengeKey::new(params.year, new.day as u8);
+    for change in scaffold_day(Path::new(SOURCE_DIR), key, new.module.as_deref())? {
+        println!("{}", change);
+    }
+    println!(
+        "{}",
+        add_placeholder_challenge(&challenges_file_path(params), key)?
+    );
+    Ok(())
+}
+
+/// Creates the solution for `key` from the template, declares its modules and registers it, and
+/// creates `sub::<module>` if asked. Returns what was created and updated.
+fn scaffold_day(src: &Path, key: ChallengeKey, module: Option<&str>) -> Result<Vec<String>> {
+    let solution_dir = src.join("aoc").join("solution");
+    let registry_path = solution_dir.join("registry.rs");
+    if !registry_path.is_file() {
+        return Err(Box::new(Error::new(&format!(
+            "{} not found! Run new from the root of the repository.",
+            registry_path.display()
+        ))));
+    }
+    let year_module = format!("year_{}", key.year);
+    let day_module = format!("day_{}", key.day);
+    let day_path = solution_dir
+        .join(&year_module)
+        .join(format!("{}.rs", day_module));
+    let sub_path = module.map(|module| src.join("sub").join(format!("{}.rs", module)));
+    for path in [Some(&day_path), sub_path.as_ref()].into_iter().flatten() {
+        if path.exists() {
+            return Err(Box::new(Error::new(&format!(
+                "{} already exists!",
+                path.display()
+            ))));
+        }
+    }
+
+    // Work out every change before writing any, so a malformed file leaves the tree as it was.
+    let mut created: Vec<(PathBuf, String)> = vec![(
+        day_path,
+        DAY_TEMPLATE.replace("${DAY}", &key.day.to_string()),
+    )];
+    let mut updated: Vec<(PathBuf, String)> = vec![];
+    let year_path = solution_dir.join(format!("{}.rs", year_module));
+    let day_line = format!("pub mod {};", day_module);
+    if year_path.exists() {
+        let year_source = fs::read_to_string(&year_path)?;
+        updated.push((
+            year_path,
+            insert_mod_line(&year_source, "pub mod ", &day_line),
+        ));
+    } else {
+        created.push((
+            year_path,
+            format!(
+                "{}\n\n/// The event these solutions are for.\nconst YEAR: u16 = {};\n",
+                day_line, key.year
+            ),
+        ));
+        let solution_path = src.join("aoc").join("solution.rs");
+        let solution_source = fs::read_to_string(&solution_path)?;
+        let year_line = format!("mod {};", year_module);
+        updated.push((
+            solution_path,
+            insert_mod_line(&solution_source, "mod year_", &year_line),
+        ));
+    }
+    let registry_source = fs::read_to_string(&registry_path)?;
+    updated.push((registry_path, register_solution(&registry_source, key)?));
+    if let (Some(module), Some(sub_path)) = (module, sub_path) {
+        created.push((sub_path, String::from(SUB_MODULE_TEMPLATE)));
+        let sub_root = src.join("sub.rs");
+        let sub_source = fs::read_to_string(&sub_root)?;
+        let module_line = format!("pub mod {};", module);
+        updated.push((
+            sub_root,
+            insert_mod_line(&sub_source, "pub mod ", &module_line),
+        ));
+    }
+
+    let mut changes = vec![];
+    for (verb, files) in [("Created", created), ("Updated", updated)] {
+        for (path, contents) in files {
+            if let Some(dir) = path.parent() {
+                fs::create_dir_all(dir)?;
+            }
+            fs::write(&path, contents)?;
+            changes.push(format!("{} {}.", verb, path.display()));
+        }
+    }
+    Ok(changes)
+}
+
+/// Adds `line` to the first block of lines starting with `prefix`, keeping the block sorted the
+/// way rustfmt sorts module declarations. Without such a block the line goes first.
+fn insert_mod_line(source: &str, prefix: &str, line: &str) -> String {
+    let mut lines: Vec<&str> = source.lines().collect();
+    if !lines.contains(&line) {
+        match lines
+            .iter()
+            .position(|existing| existing.starts_with(prefix))
+        {
+            Some(start) => {
+                let end = start
+                    + lines[start..]
+                        .iter()
+                        .take_while(|existing| existing.starts_with(prefix))
+                        .count();
+                lines.insert(end, line);
+                lines[start..=end].sort_unstable();
+            }
+            None => lines.insert(0, line),
+        }
+    }
+    lines.join("\n") + "\n"
+}
+
+/// Adds the solution for `key` to the registry's `SOLUTIONS` list in year and day order, and
+/// imports the module of every year in the list.
+fn register_solution(source: &str, key: ChallengeKey) -> Result<String> {
+    let malformed = || Error::new("The registry's SOLUTIONS list is not in the expected form!");
+    let mut lines: Vec<String> = source.lines().map(String::from).collect();
+    let start = 1 + lines
+        .iter()
+        .position(|line| line.starts_with("static SOLUTIONS"))
+        .ok_or_else(malformed)?;
+    let end = start
+        + lines[start..]
+            .iter()
+            .position(|line| line.trim() == "];")
+            .ok_or_else(malformed)?;
+    let mut entries: Vec<(ChallengeKey, String)> = lines[start..end]
+        .iter()
+        .map(|line| Ok((registered_key(line).ok_or_else(malformed)?, line.clone())))
+        .collect::<Result<_>>()?;
+    if entries.iter().any(|(registered, _)| *registered == key) {
+        return Err(Box::new(Error::new(&format!(
+            "{} is already registered!",
+            key
+        ))));
+    }
+    entries.push((
+        key,
+        format!("    year_{}::day_{}::Day{},", key.year, key.day, key.day),
+    ));
+    entries.sort_by_key(|(registered, _)| *registered);
+
+    let years: BTreeSet<String> = entries
+        .iter()
+        .map(|(registered, _)| format!("year_{}", registered.year))
+        .collect();
+    let imports = match years.len() {
+        1 => format!("use super::{};", years.iter().next().unwrap()),
+        _ => format!(
+            "use super::{{{}}};",
+            years.into_iter().collect::<Vec<String>>().join(", ")
+        ),
+    };
+    lines.splice(start..end, entries.into_iter().map(|(_, line)| line));
+    let import_line = lines
+        .iter_mut()
+        .find(|line| line.starts_with("use super::year_") || line.starts_with("use super::{year_"))
+        .ok_or_else(malformed)?;
+    *import_line = imports;
+    Ok(lines.join("\n") + "\n")
+}
+
+/// The key of a `SOLUTIONS` entry such as `year_2021::day_3::Day3,`.
+fn registered_key(line: &str) -> Option<ChallengeKey> {
+    let mut path = line.trim().strip_prefix("year_")?.split("::");
+    let year = path.next()?.parse().ok()?;
+    let day = path.next()?.strip_prefix("day_")?.parse().ok()?;
+    Some(ChallengeKey::new(year, day))
+}
+
+/// Adds an empty challenge for `key` to the store at `path`, so the day is listed and can be
+/// imported into. A challenge that is already stored is left as it is.
+fn add_placeholder_challenge(path: &Path, key: ChallengeKey) -> Result<String> {
+    let already_stored = format!(
+        "Challenge {} is already in {}, so it was left as it is.",
+        key,
+        path.display()
+    );
+    if path.is_dir() {
+        let mut source = DirectorySource::new(path.to_path_buf());
+        if source.find_challenge(key)?.is_some() {
+            return Ok(already_stored);
+        }
+        source.save_data(key, String::new())?;
+    } else {
+        let mut challenges: Map<String, Value> = if path.exists() {
+            nest_by_year(serde_json::from_str(&fs::read_to_string(path)?)?)
+        } else {
+            Map::new()
+        };
+        let stored = challenges
+            .get(&key.year.to_string())
+            .and_then(|year| year.get(key.day.to_string()));
+        if stored.is_some() {
+            return Ok(already_stored);
+        }
+        let placeholder = ImportedChallenge {
+            key,
+            parts: None,
+            examples: vec![],
+            data: None,
+        };
+        import_challenge(&mut challenges, placeholder, false)?;
+        write_challenges(path, &challenges)?;
+    }
+    Ok(format!(
+        "Added a placeholder for challenge {} to {}. It has no example data or puzzle input yet; \
+         add them with import, or get the input with fetch.",
+        key,
+        path.display()
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aoc::import::run_import;
+    use crate::aoc::solution::resolve_dataset;
+    use crate::aoc::source::JsonFileSource;
+    use crate::aoc::test_dir::TestDir;
+    use crate::cli::parse_args;
+    use crate::common::Command;
+    use crate::config::Config;
+
+    const REGISTRY: &str = "\
+use super::year_2021;
+use super::{Answer, ChallengeSolution, ParsedInput, Solution};
+
+static SOLUTIONS: &[&dyn AnySolution] = solutions![
+    year_2021::day_1::Day1,
+    year_2021::day_3::Day3,
+];
+";
+
+    #[test]
+    fn solutions_are_registered_in_year_and_day_order() -> Result<()> {
+        let registry = register_solution(REGISTRY, ChallengeKey::new(2021, 2))?;
+        assert_eq!(
+            REGISTRY.replace("Day1,\n", "Day1,\n    year_2021::day_2::Day2,\n"),
+            registry
+        );
+        let registry = register_solution(&registry, ChallengeKey::new(2016, 25))?;
+        assert!(registry.starts_with("use super::{year_2016, year_2021};\n"));
+        assert!(registry.contains("[\n    year_2016::day_25::Day25,\n    year_2021::day_1::Day1,"));
+        assert_eq!(
+            "2021 day 3 is already registered!",
+            register_solution(REGISTRY, ChallengeKey::new(2021, 3))
+                .unwrap_err()
+                .to_string()
+        );
+        // The real registry has to stay in the form that new can edit.
+        register_solution(
+            include_str!("solution/registry.rs"),
+            ChallengeKey::new(2099, 1),
+        )?;
+        Ok(())
+    }
+
+    #[test]
+    fn mod_lines_are_kept_sorted() {
+        let source = "mod registry;\nmod year_2021;\n\nuse std::any::Any;\n";
+        assert_eq!(
+            "mod registry;\nmod year_2016;\nmod year_2021;\n\nuse std::any::Any;\n",
+            insert_mod_line(source, "mod year_", "mod year_2016;")
+        );
+        assert_eq!(
+            source,
+            insert_mod_line(source, "mod year_", "mod year_2021;")
+        );
+    }
+
+    #[test]
+    fn new_days_are_scaffolded_into_the_source_tree() -> Result<()> {
+        let src = TestDir::new("scaffold_src");
+        let solution_dir = src.join("aoc").join("solution");
+        fs::create_dir_all(&solution_dir)?;
+        fs::write(
+            src.join("aoc").join("solution.rs"),
+            "mod registry;\nmod year_2021;\n",
+        )?;
+        fs::write(solution_dir.join("registry.rs"), REGISTRY)?;
+        fs::write(src.join("sub.rs"), "pub mod bingo;\npub mod scanning;\n")?;
+
+        let key = ChallengeKey::new(2022, 13);
+        assert_eq!(6, scaffold_day(&src, key, Some("sonar"))?.len());
+        let day = fs::read_to_string(solution_dir.join("year_2022").join("day_13.rs"))?;
+        assert!(day.contains("pub struct Day13;\n") && day.contains("const DAY: u8 = 13;\n"));
+        assert!(!day.contains("${"));
+        assert_eq!(
+            "pub mod day_13;\n\n/// The event these solutions are for.\nconst YEAR: u16 = 2022;\n",
+            fs::read_to_string(solution_dir.join("year_2022.rs"))?
+        );
+        assert_eq!(
+            "mod registry;\nmod year_2021;\nmod year_2022;\n",
+            fs::read_to_string(src.join("aoc").join("solution.rs"))?
+        );
+        assert_eq!(
+            "pub mod bingo;\npub mod scanning;\npub mod sonar;\n",
+            fs::read_to_string(src.join("sub.rs"))?
+        );
+        let sub_module = fs::read_to_string(src.join("sub").join("sonar.rs"))?;
+        assert!(sub_module.contains("#[ignore") && sub_module.contains("solve(TEST_STR)"));
+
+        assert_eq!(
+            3,
+            scaffold_day(&src, ChallengeKey::new(2022, 2), None)?.len()
+        );
+        assert!(scaffold_day(&src, key, None).is_err());
+        Ok(())
+    }
+
+    fn parse(line: &str) -> Result<AppParams> {
+        parse_args(
+            line.split_whitespace().map(String::from),
+            &Config::default(),
+        )
+    }
+
+    #[test]
+    fn placeholder_challenges_are_added_once() -> Result<()> {
+        let dir = TestDir::new("scaffold_challenges");
+        let path = dir.join("challenges.json");
+        let key = ChallengeKey::new(2022, 13);
+        assert!(add_placeholder_challenge(&path, key)?.starts_with("Added"));
+        let challenge = JsonFileSource::open(&path)?.find_challenge(key)?.unwrap();
+        assert_eq!((2, ""), (challenge.parts, challenge.data.as_str()));
+        let params = parse("aoc2021 run --day 13")?;
+        assert_eq!(
+            "Challenge 2022 day 13 has no example data or puzzle input yet! Add them with import, \
+             or get the input with fetch.",
+            resolve_dataset(&challenge, &params)
+                .unwrap_err()
+                .to_string()
+        );
+        assert!(add_placeholder_challenge(&path, key)?.contains("already"));
+        Ok(())
+    }
+
+    #[test]
+    fn placeholders_can_be_imported_into() -> Result<()> {
+        let dir = TestDir::new("scaffold_import");
+        let path = dir.join("challenges.json");
+        let data_path = dir.join("day_13.txt");
+        fs::write(&data_path, "1\n2\n")?;
+        let key = ChallengeKey::new(2022, 13);
+        add_placeholder_challenge(&path, key)?;
+        let line = format!(
+            "aoc2021 import --year 2022 --day 13 --data {} --challenges {}",
+            data_path.display(),
+            path.display()
+        );
+        let params = parse(&line)?;
+        let import = match &params.command {
+            Command::Import(import) => import,
+            _ => return Err(Box::new(Error::new("Not an import command!"))),
+        };
+        run_import(&params, import)?;
+        let challenge = JsonFileSource::open(&path)?.find_challenge(key)?.unwrap();
+        assert_eq!("1\n2\n", challenge.data);
+        // Once it has data, it is no longer a placeholder.
+        assert!(run_import(&params, import).is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,80 @@
+use std::collections::BTreeMap;
+use std::ops::RangeInclusive;
+
+use super::challenge::{Challenge, ChallengeKey};
+use super::solution::{resolve_dataset, solution_registry};
+use super::source::{get_challenge, ChallengeSource};
+use crate::{AppParams, Result};
+
+/// Prints a table of the stored challenges in `days` of `year`, with the parts that have
+/// solutions.
+pub fn run_list(source: &dyn ChallengeSource, year: u16, days: RangeInclusive<u8>) -> Result<()> {
+    let solutions = solution_registry()?;
+    println!(" Day | Parts | Solutions | Examples | Input");
+    println!("-----+-------+-----------+----------+-------");
+    for day in days {
+        let key = ChallengeKey::new(year, day);
+        let challenge = match source.find_challenge(key)? {
+            Some(challenge) => challenge,
+            None => continue,
+        };
+        let solutions: Vec<String> = (1..=challenge.parts)
+            .filter(|&part| solutions.get_part(key, part).is_some())
+            .map(|part| part.to_string())
+            .collect();
+        let examples: Vec<&str> = challenge
+            .examples
+            .iter()
+            .map(|example| example.name.as_str())
+            .collect();
+        println!(
+            " {:>3} | {:>5} | {:<9} | {:<8} | {}",
+            day,
+            challenge.parts,
+            or_none(solutions.join(", ")),
+            or_none(examples.join(", ")),
+            if challenge.data.is_empty() {
+                String::from("-")
+            } else {
+                format!("{} bytes", challenge.data.len())
+            }
+        );
+    }
+    Ok(())
+}
+
+/// Prints the examples and puzzle data for `key`. With `--example` or `--use-example-data`
+/// only that example's data is printed, as-is, so it can be piped elsewhere.
+pub fn run_show(source: &dyn ChallengeSource, key: ChallengeKey, params: &AppParams) -> Result<()> {
+    let challenge = get_challenge(source, key)?;
+    if params.example.is_some() || params.use_example_data {
+        let dataset = resolve_dataset(&challenge, params)?;
+        println!("{}", challenge.data_for(dataset));
+        return Ok(());
+    }
+    for example in &challenge.examples {
+        println!("Example {}:", example.name);
+        print_answers(&challenge, &example.answers);
+        println!("{}\n", example.data);
+    }
+    println!("Puzzle data:");
+    print_answers(&challenge, &challenge.answers);
+    println!("{}", challenge.data);
+    Ok(())
+}
+
+fn print_answers(challenge: &Challenge, answers: &BTreeMap<u8, String>) {
+    for part in 1..=challenge.parts {
+        if let Some(answer) = answers.get(&part) {
+            println!("  Part {} answer: {}", part, answer);
+        }
+    }
+}
+
+fn or_none(s: String) -> String {
+    if s.is_empty() {
+        String::from("-")
+    } else {
+        s
+    }
+}
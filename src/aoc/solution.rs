@@ -1,28 +1,142 @@
-mod solutions_implemented;
+mod registry;
+mod year_2021;
 
+use super::answer::Answer;
 use super::challenge::Challenge;
-use crate::{AppParams, Result};
-pub use solutions_implemented::get_challenge_solution;
+use crate::common::day_params::day_param;
+use crate::common::Parse;
+use crate::{AppParams, Error, Result};
+use registry::AnySolution;
+pub use registry::{solution_registry, Registry};
+use std::any::Any;
+use std::panic::{self, AssertUnwindSafe};
 
-type SolutionFn = fn(String) -> Result<()>;
+pub type ParsedInput = Box<dyn Any>;
+
+/// Solves one part of a challenge from its parsed input.
+pub type PartFn<I> = fn(&I) -> Result<Answer>;
+
+/// A day's solution: the input it parses the challenge data into, once, and a solver for each of
+/// the day's parts. Solutions live in a module per year and are listed in `registry.rs`; `new`
+/// adds the days it creates there, and a test checks that every day's file is listed.
+pub trait Solution {
+    const YEAR: u16;
+    const DAY: u8;
+    type Input: Parse + 'static;
+    /// The solvers for parts 1, 2 and so on.
+    const PARTS: &'static [PartFn<Self::Input>];
+
+    fn parse(challenge_data: &str) -> Result<Self::Input> {
+        Self::Input::parse(challenge_data)
+    }
+
+    /// The setting `name` for this day from the config file's `[params]`, or `default`.
+    fn param<T: TryFrom<u64>>(name: &str, default: T) -> Result<T> {
+        match day_param(Self::YEAR, Self::DAY, name) {
+            None => Ok(default),
+            Some(value) => match T::try_from(value) {
+                Ok(value) => Ok(value),
+                Err(_) => Err(Box::new(Error::new(&format!(
+                    "Setting {} = {} is out of range for {} day {}!",
+                    name,
+                    value,
+                    Self::YEAR,
+                    Self::DAY
+                )))),
+            },
+        }
+    }
+}
+
+/// Which of a challenge's inputs to use: one of its examples, by index, or the puzzle data.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Dataset {
+    Example(usize),
+    Real,
+}
+
+/// A registered day's solution, with its input type hidden behind `ParsedInput`.
+#[derive(Clone, Copy)]
 pub struct ChallengeSolution {
-    solution_fn: SolutionFn,
+    solution: &'static dyn AnySolution,
 }
 
 impl ChallengeSolution {
-    fn new(solution_fn: SolutionFn) -> ChallengeSolution {
-        ChallengeSolution { solution_fn }
+    pub fn has_part(&self, part: u8) -> bool {
+        (1..=self.solution.parts()).contains(&part)
     }
 
-    pub fn run(&self, challenge: Challenge, params: AppParams) -> Result<()> {
-        (self.solution_fn)(resolve_challenge_data(challenge, params))
+    pub fn parse(&self, challenge_data: &str) -> Result<ParsedInput> {
+        catch_panic(|| self.solution.parse(challenge_data))
+    }
+
+    /// Solves `part` from input returned by `parse`, which can be shared by every part.
+    pub fn solve(&self, part: u8, input: &ParsedInput) -> Result<Answer> {
+        catch_panic(|| self.solution.solve(part, input))
+    }
+
+    pub fn run(&self, challenge: &Challenge, params: &AppParams, part: u8) -> Result<Answer> {
+        self.run_on(challenge, resolve_dataset(challenge, params)?, part)
+    }
+
+    pub fn run_on(&self, challenge: &Challenge, dataset: Dataset, part: u8) -> Result<Answer> {
+        self.solve(part, &self.parse(challenge.data_for(dataset))?)
     }
 }
 
-fn resolve_challenge_data(challenge: Challenge, params: AppParams) -> String {
-    if params.use_example_data || challenge.data == "" {
-        challenge.example_data
-    } else {
-        challenge.data
+/// Runs solution code, turning a panic into an error, so one broken day fails on its own rather
+/// than taking a whole batch down with it.
+fn catch_panic<T>(run: impl FnOnce() -> Result<T>) -> Result<T> {
+    match panic::catch_unwind(AssertUnwindSafe(run)) {
+        Ok(result) => result,
+        Err(payload) => {
+            let cause = match (
+                payload.downcast_ref::<&str>(),
+                payload.downcast_ref::<String>(),
+            ) {
+                (Some(message), _) => message.to_string(),
+                (_, Some(message)) => message.clone(),
+                _ => String::from("no message"),
+            };
+            Err(Box::new(Error::new(&format!(
+                "The solution panicked: {}",
+                cause
+            ))))
+        }
+    }
+}
+
+pub fn resolve_dataset(challenge: &Challenge, params: &AppParams) -> Result<Dataset> {
+    if let Some(name) = &params.example {
+        return match challenge.example_index(name) {
+            Some(index) => Ok(Dataset::Example(index)),
+            None => Err(Box::new(Error::new(&format!(
+                "Challenge {} has no example named {}! Available examples: {}",
+                challenge.key(),
+                name,
+                challenge
+                    .examples
+                    .iter()
+                    .map(|example| example.name.as_str())
+                    .collect::<Vec<&str>>()
+                    .join(", ")
+            )))),
+        };
+    }
+    if !params.use_example_data && !challenge.data.is_empty() {
+        return Ok(Dataset::Real);
+    }
+    if challenge.examples.is_empty() {
+        let missing = if challenge.data.is_empty() {
+            "example data or puzzle input yet! Add them with import, or get the input with fetch."
+        } else {
+            "example data! Add an example with import --example."
+        };
+        return Err(Box::new(Error::new(&format!(
+            "Challenge {} has no {}",
+            challenge.key(),
+            missing
+        ))));
     }
+    Ok(Dataset::Example(0))
 }
@@ -0,0 +1,335 @@
+use std::collections::BTreeMap;
+use std::marker::PhantomData;
+use std::sync::OnceLock;
+
+use super::year_2021;
+use super::{Answer, ChallengeSolution, ParsedInput, Solution};
+use crate::aoc::challenge::ChallengeKey;
+use crate::cli::{ALL_CHALLENGE_DAYS, FIRST_YEAR};
+use crate::{Error, Result};
+
+macro_rules! solutions {
+    ($($solution:ty),* $(,)?) => {
+        &[$(&Registered::<$solution>(PhantomData)),*]
+    };
+}
+
+/// Every implemented solution, by year. A year and day may only appear once.
+static SOLUTIONS: &[&dyn AnySolution] = solutions![
+    year_2021::day_1::Day1,
+    year_2021::day_2::Day2,
+    year_2021::day_3::Day3,
+    year_2021::day_4::Day4,
+    year_2021::day_5::Day5,
+    year_2021::day_6::Day6,
+    year_2021::day_7::Day7,
+    year_2021::day_8::Day8,
+    year_2021::day_9::Day9,
+    year_2021::day_10::Day10,
+    year_2021::day_11::Day11,
+    year_2021::day_12::Day12,
+];
+
+/// A `Solution` with its input type erased, so every day can be kept in one registry.
+pub trait AnySolution: Sync {
+    fn key(&self) -> ChallengeKey;
+    fn parts(&self) -> u8;
+    fn parse(&self, challenge_data: &str) -> Result<ParsedInput>;
+    fn solve(&self, part: u8, input: &ParsedInput) -> Result<Answer>;
+}
+
+struct Registered<S>(PhantomData<fn() -> S>);
+
+impl<S: Solution> AnySolution for Registered<S> {
+    fn key(&self) -> ChallengeKey {
+        ChallengeKey::new(S::YEAR, S::DAY)
+    }
+
+    fn parts(&self) -> u8 {
+        S::PARTS.len() as u8
+    }
+
+    fn parse(&self, challenge_data: &str) -> Result<ParsedInput> {
+        Ok(Box::new(S::parse(challenge_data)?))
+    }
+
+    fn solve(&self, part: u8, input: &ParsedInput) -> Result<Answer> {
+        let solve_part = match (part as usize).checked_sub(1).and_then(|i| S::PARTS.get(i)) {
+            Some(solve_part) => solve_part,
+            None => {
+                return Err(Box::new(Error::SolutionNotFound {
+                    year: S::YEAR,
+                    day: S::DAY,
+                    part,
+                }))
+            }
+        };
+        match input.downcast_ref::<S::Input>() {
+            Some(input) => solve_part(input),
+            None => Err(Box::new(Error::new(&format!(
+                "Parsed input is not the input of {}!",
+                self.key()
+            )))),
+        }
+    }
+}
+
+/// The solutions by year and day, checked so that each day has exactly one solution with at
+/// least one part.
+pub struct Registry {
+    solutions: BTreeMap<ChallengeKey, &'static dyn AnySolution>,
+}
+
+impl Registry {
+    fn new(solutions: &[&'static dyn AnySolution]) -> Result<Registry> {
+        let mut by_key: BTreeMap<ChallengeKey, &'static dyn AnySolution> = BTreeMap::new();
+        for &solution in solutions {
+            let key = solution.key();
+            if key.year < FIRST_YEAR {
+                return Err(Box::new(Error::new(&format!(
+                    "A solution is registered for {}, but the first event was in {}!",
+                    key, FIRST_YEAR
+                ))));
+            }
+            if !ALL_CHALLENGE_DAYS.contains(&(key.day as u32)) {
+                return Err(Box::new(Error::new(&format!(
+                    "A solution is registered for {}, but days are 1-25!",
+                    key
+                ))));
+            }
+            if solution.parts() == 0 {
+                return Err(Box::new(Error::new(&format!(
+                    "The solution for {} has no parts!",
+                    key
+                ))));
+            }
+            if by_key.insert(key, solution).is_some() {
+                return Err(Box::new(Error::new(&format!(
+                    "More than one solution is registered for {}!",
+                    key
+                ))));
+            }
+        }
+        Ok(Registry { solutions: by_key })
+    }
+
+    pub fn get(&self, key: ChallengeKey) -> Option<ChallengeSolution> {
+        self.solutions
+            .get(&key)
+            .map(|&solution| ChallengeSolution { solution })
+    }
+
+    /// The solution for `key`, if it solves `part`.
+    pub fn get_part(&self, key: ChallengeKey, part: u8) -> Option<ChallengeSolution> {
+        self.get(key).filter(|solution| solution.has_part(part))
+    }
+}
+
+/// The registry of every implemented solution, or why it is malformed.
+pub fn solution_registry() -> Result<&'static Registry> {
+    static REGISTRY: OnceLock<std::result::Result<Registry, String>> = OnceLock::new();
+    match REGISTRY.get_or_init(|| Registry::new(SOLUTIONS).map_err(|err| err.to_string())) {
+        Ok(registry) => Ok(registry),
+        Err(message) => Err(Box::new(Error::new(message))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::Path;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+    use crate::aoc::challenge::{Challenge, Example};
+    use crate::aoc::solution::{Dataset, PartFn};
+    use crate::common::day_params::set_day_params;
+    use crate::common::{AllDayParams, DayParams, Parse};
+
+    static PARSE_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+    struct Words(Vec<String>);
+
+    impl Parse for Words {
+        fn parse(input: &str) -> Result<Self> {
+            PARSE_COUNT.fetch_add(1, Ordering::Relaxed);
+            Ok(Words(input.split_whitespace().map(String::from).collect()))
+        }
+    }
+
+    const WORD_PARTS: &[PartFn<Words>] = &[
+        |words| Ok(words.0.len().into()),
+        |words| Ok(words.0.concat().into()),
+        |words| Ok(words.0.join("-").into()),
+    ];
+
+    /// Solves the first `PARTS` of `WORD_PARTS` for `DAY` of `YEAR`.
+    struct WordCount<const YEAR: u16, const DAY: u8, const PARTS: usize>;
+
+    impl<const YEAR: u16, const DAY: u8, const PARTS: usize> Solution for WordCount<YEAR, DAY, PARTS> {
+        const YEAR: u16 = YEAR;
+        const DAY: u8 = DAY;
+        type Input = Words;
+        const PARTS: &'static [PartFn<Words>] = WORD_PARTS.split_at(PARTS).0;
+    }
+
+    #[test]
+    fn input_is_parsed_once_for_every_part() -> Result<()> {
+        let key = ChallengeKey::new(2021, 20);
+        let registry = Registry::new(solutions![WordCount<2021, 20, 3>])?;
+        let solution = registry.get(key).unwrap();
+        assert!(registry.get(ChallengeKey::new(2022, 20)).is_none());
+        assert!(registry.get_part(key, 4).is_none());
+        let challenge = Challenge {
+            examples: vec![Example::new("example", String::from("a b c"))],
+            ..Challenge::new(key)
+        };
+        let input = solution.parse(challenge.data_for(Dataset::Example(0)))?;
+        let answers: Vec<String> = (1..=3)
+            .map(|part| Ok(solution.solve(part, &input)?.to_string()))
+            .collect::<Result<Vec<String>>>()?;
+        assert_eq!(vec!["3", "abc", "a-b-c"], answers);
+        assert_eq!(1, PARSE_COUNT.load(Ordering::Relaxed));
+        Ok(())
+    }
+
+    #[test]
+    fn malformed_and_duplicate_days_are_reported() {
+        let message = |solutions: &[&'static dyn AnySolution]| {
+            Registry::new(solutions).err().unwrap().to_string()
+        };
+        assert_eq!(
+            "More than one solution is registered for 2021 day 3!",
+            message(solutions![
+                WordCount<2021, 3, 2>,
+                WordCount<2022, 3, 1>,
+                WordCount<2021, 3, 1>
+            ])
+        );
+        assert_eq!(
+            "A solution is registered for 2021 day 26, but days are 1-25!",
+            message(solutions![WordCount<2021, 26, 2>])
+        );
+        assert_eq!(
+            "A solution is registered for 2014 day 1, but the first event was in 2015!",
+            message(solutions![WordCount<2014, 1, 2>])
+        );
+        assert_eq!(
+            "The solution for 2021 day 5 has no parts!",
+            message(solutions![WordCount<2021, 5, 0>])
+        );
+    }
+
+    struct Digits(Vec<u32>);
+
+    impl Parse for Digits {
+        fn parse(input: &str) -> Result<Self> {
+            Ok(Digits(
+                input.chars().filter_map(|c| c.to_digit(10)).collect(),
+            ))
+        }
+    }
+
+    /// Solves its only part by indexing past the end of short inputs.
+    struct Panicking;
+
+    impl Solution for Panicking {
+        const YEAR: u16 = 2015;
+        const DAY: u8 = 1;
+        type Input = Digits;
+        const PARTS: &'static [PartFn<Digits>] = &[|digits| Ok(digits.0[3].into())];
+    }
+
+    #[test]
+    fn panics_in_a_solution_are_reported_as_errors() -> Result<()> {
+        let registry = Registry::new(solutions![Panicking])?;
+        let solution = registry.get(ChallengeKey::new(2015, 1)).unwrap();
+        let input = solution.parse("123")?;
+        let message = solution.solve(1, &input).unwrap_err().to_string();
+        assert!(message.starts_with("The solution panicked: index out of bounds"));
+        Ok(())
+    }
+
+    #[test]
+    fn settings_are_read_for_the_solution_day() -> Result<()> {
+        set_day_params(&AllDayParams::from([(
+            (2015, 25),
+            DayParams::from([(String::from("steps"), 300)]),
+        )]));
+        type Day = WordCount<2015, 25, 1>;
+        assert_eq!(300, Day::param("steps", 10u32)?);
+        assert_eq!(7, Day::param("size", 7u32)?);
+        assert_eq!(10, WordCount::<2015, 24, 1>::param("steps", 10u32)?);
+        assert_eq!(
+            "Setting steps = 300 is out of range for 2015 day 25!",
+            Day::param("steps", 10u8).unwrap_err().to_string()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn small_octopus_grids_synchronise() -> Result<()> {
+        let solution = solution_registry()?
+            .get(ChallengeKey::new(2021, 11))
+            .unwrap();
+        let input = solution.parse("1")?;
+        assert_eq!("9", solution.solve(2, &input)?.to_string());
+        Ok(())
+    }
+
+    #[test]
+    fn well_formed_inputs_without_an_answer_are_errors() -> Result<()> {
+        let registry = solution_registry()?;
+        for (day, data, message) in [
+            (
+                9,
+                "1",
+                "The three largest basins are needed, but there are only 1!",
+            ),
+            (10, "()", "No line is incomplete!"),
+        ] {
+            let solution = registry.get(ChallengeKey::new(2021, day)).unwrap();
+            let input = solution.parse(data)?;
+            assert_eq!(message, solution.solve(2, &input).unwrap_err().to_string());
+        }
+        Ok(())
+    }
+
+    /// The days under each `year_YYYY` module, going by their `day_N.rs` files.
+    fn implemented_days() -> Result<Vec<ChallengeKey>> {
+        let solution_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("src/aoc/solution");
+        let mut days = vec![];
+        for year_entry in fs::read_dir(solution_dir)? {
+            let year_dir = year_entry?.path();
+            let year = year_dir
+                .file_name()
+                .and_then(|name| name.to_str()?.strip_prefix("year_")?.parse::<u16>().ok());
+            let year = match year {
+                Some(year) if year_dir.is_dir() => year,
+                _ => continue,
+            };
+            for day_entry in fs::read_dir(&year_dir)? {
+                let name = day_entry?.file_name();
+                let day = name.to_str().and_then(|name| {
+                    name.strip_prefix("day_")?
+                        .strip_suffix(".rs")?
+                        .parse::<u8>()
+                        .ok()
+                });
+                days.extend(day.map(|day| ChallengeKey::new(year, day)));
+            }
+        }
+        days.sort();
+        Ok(days)
+    }
+
+    #[test]
+    fn every_implemented_day_is_registered() -> Result<()> {
+        // new registers the days it creates, but days written by hand have to be added to
+        // SOLUTIONS too, as the day their file is named after.
+        let registered: Vec<ChallengeKey> =
+            solution_registry()?.solutions.keys().copied().collect();
+        assert_eq!(implemented_days()?, registered);
+        Ok(())
+    }
+}
@@ -0,0 +1,31 @@
+use crate::aoc::solution::{PartFn, Solution};
+use crate::aoc::Answer;
+use crate::common::parsing::{int, lines, parse_input};
+use crate::common::Parse;
+use crate::sub::scanning;
+use crate::Result;
+
+pub type Input = Vec<i32>;
+
+impl Parse for Input {
+    fn parse(input: &str) -> Result<Self> {
+        parse_input(input, lines(int()))
+    }
+}
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    const YEAR: u16 = super::YEAR;
+    const DAY: u8 = 1;
+    type Input = Input;
+    const PARTS: &'static [PartFn<Input>] = &[solve_part_1, solve_part_2];
+}
+
+fn solve_part_1(depths: &Input) -> Result<Answer> {
+    Ok(scanning::run_depth_scan(depths.iter().copied().map(Ok), 1)?.into())
+}
+
+fn solve_part_2(depths: &Input) -> Result<Answer> {
+    Ok(scanning::run_depth_scan(depths.iter().copied().map(Ok), 3)?.into())
+}
@@ -0,0 +1,68 @@
+use crate::aoc::solution::{PartFn, Solution};
+use crate::aoc::Answer;
+use crate::common::Parse;
+use crate::sub::navigation::{
+    calculate_completion_score, parse_chunks_for_lines, ChunkStyle, ParseError, ParseResult,
+};
+use crate::{Error, Result};
+
+pub type Input = Vec<ParseResult>;
+
+impl Parse for Input {
+    fn parse(input: &str) -> Result<Self> {
+        Ok(parse_chunks_for_lines(input))
+    }
+}
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    const YEAR: u16 = super::YEAR;
+    const DAY: u8 = 10;
+    type Input = Input;
+    const PARTS: &'static [PartFn<Input>] = &[solve_part_1, solve_part_2];
+}
+
+fn solve_part_1(line_results: &Input) -> Result<Answer> {
+    let unexpected_styles: Vec<ChunkStyle> = line_results
+        .iter()
+        .filter_map(|line| match line {
+            Err(err) => match err {
+                ParseError::EncounteredStyle(style) => Some(*style),
+                _ => None,
+            },
+            _ => None,
+        })
+        .collect();
+    let error_score = unexpected_styles.iter().fold(0, |acc, style| {
+        acc + match style {
+            ChunkStyle::Paren => 3,
+            ChunkStyle::Square => 57,
+            ChunkStyle::Curly => 1197,
+            ChunkStyle::Angle => 25137,
+        }
+    });
+    Ok(Answer::from(error_score))
+}
+
+fn solve_part_2(line_results: &Input) -> Result<Answer> {
+    let unexpected_eol_stacks: Vec<Vec<char>> = line_results
+        .iter()
+        .filter_map(|line| match line {
+            Err(err) => match err {
+                ParseError::EOL(stack) => Some(stack.clone()),
+                _ => None,
+            },
+            _ => None,
+        })
+        .collect();
+    let mut completion_scores: Vec<u64> = unexpected_eol_stacks
+        .into_iter()
+        .map(|mut stack| calculate_completion_score(&mut stack))
+        .collect();
+    completion_scores.sort();
+    match completion_scores.get(completion_scores.len().saturating_sub(1) / 2) {
+        Some(&middle_score) => Ok(middle_score.into()),
+        None => Err(Box::new(Error::invalid_data("No line is incomplete!"))),
+    }
+}
@@ -0,0 +1,37 @@
+use crate::aoc::solution::{PartFn, Solution};
+use crate::aoc::Answer;
+use crate::common::trace::with_tracer;
+use crate::sub::modelling::Octopuses;
+use crate::Result;
+
+pub type Input = Octopuses;
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    const YEAR: u16 = super::YEAR;
+    const DAY: u8 = 11;
+    type Input = Input;
+    const PARTS: &'static [PartFn<Input>] = &[solve_part_1, solve_part_2];
+}
+
+fn solve_part_1(octopuses: &Input) -> Result<Answer> {
+    let steps = Day11::param("steps", 100)?;
+    let mut octopuses = octopuses.clone();
+    let num_flashes = with_tracer!(tracer => octopuses.simulate(steps, tracer));
+    Ok(num_flashes.into())
+}
+
+fn solve_part_2(octopuses: &Input) -> Result<Answer> {
+    let mut octopuses = octopuses.clone();
+    let num_ticks = with_tracer!(tracer => {
+        let mut num_ticks = 0;
+        let mut last_flashes = 0;
+        while last_flashes < octopuses.count() {
+            last_flashes = octopuses.tick(tracer);
+            num_ticks += 1;
+        }
+        num_ticks
+    });
+    Ok(Answer::from(num_ticks))
+}
@@ -0,0 +1,25 @@
+use crate::aoc::solution::{PartFn, Solution};
+use crate::aoc::Answer;
+use crate::sub::pathfinding::CaveSystem;
+use crate::Result;
+
+pub type Input = CaveSystem;
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    const YEAR: u16 = super::YEAR;
+    const DAY: u8 = 12;
+    type Input = Input;
+    const PARTS: &'static [PartFn<Input>] = &[solve_part_1, solve_part_2];
+}
+
+fn solve_part_1(cave_system: &Input) -> Result<Answer> {
+    let paths = cave_system.find_paths("start", "end", false)?;
+    Ok(paths.len().into())
+}
+
+fn solve_part_2(cave_system: &Input) -> Result<Answer> {
+    let paths = cave_system.find_paths("start", "end", true)?;
+    Ok(paths.len().into())
+}
@@ -0,0 +1,43 @@
+use crate::aoc::solution::{PartFn, Solution};
+use crate::aoc::Answer;
+use crate::common::Parse;
+use crate::sub::movement as sub_movement;
+use crate::sub::{Direction, Position};
+use crate::Result;
+
+pub type Input = Vec<(Direction, i32)>;
+
+impl Parse for Input {
+    fn parse(input: &str) -> Result<Self> {
+        sub_movement::parse_instructions(input)
+    }
+}
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    const YEAR: u16 = super::YEAR;
+    const DAY: u8 = 2;
+    type Input = Input;
+    const PARTS: &'static [PartFn<Input>] = &[solve_part_1, solve_part_2];
+}
+
+fn solve_part_1(move_instructions: &Input) -> Result<Answer> {
+    let final_position = sub_movement::run(
+        move_instructions.iter().copied(),
+        sub_movement::MovementStyle::LINEAR,
+    )?;
+    Ok(position_product(final_position))
+}
+
+fn solve_part_2(move_instructions: &Input) -> Result<Answer> {
+    let final_position = sub_movement::run(
+        move_instructions.iter().copied(),
+        sub_movement::MovementStyle::DIRECTIONAL,
+    )?;
+    Ok(position_product(final_position))
+}
+
+fn position_product(position: Position) -> Answer {
+    Answer::from(position.x as i64 * position.y as i64)
+}
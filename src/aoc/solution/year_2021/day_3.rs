@@ -0,0 +1,31 @@
+use crate::aoc::solution::{PartFn, Solution};
+use crate::aoc::Answer;
+use crate::common::bit_string::BitString;
+use crate::common::Parse;
+use crate::sub::diagnostics;
+use crate::Result;
+
+pub type Input = Vec<BitString>;
+
+impl Parse for Input {
+    fn parse(input: &str) -> Result<Self> {
+        diagnostics::parse_binary_numbers(input)
+    }
+}
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    const YEAR: u16 = super::YEAR;
+    const DAY: u8 = 3;
+    type Input = Input;
+    const PARTS: &'static [PartFn<Input>] = &[solve_part_1, solve_part_2];
+}
+
+fn solve_part_1(binary_numbers: &Input) -> Result<Answer> {
+    Ok(diagnostics::run_gamma_epsilon_report(binary_numbers)?.into())
+}
+
+fn solve_part_2(binary_numbers: &Input) -> Result<Answer> {
+    Ok(diagnostics::run_life_support_rating_report(binary_numbers)?.into())
+}
@@ -0,0 +1,53 @@
+use crate::aoc::solution::{PartFn, Solution};
+use crate::aoc::Answer;
+use crate::common::parsing::{header, int, parse_input, sections, separated, single_line};
+use crate::common::trace::with_tracer;
+use crate::common::Parse;
+use crate::sub::bingo;
+use crate::{Error, Result};
+
+pub type Input = (Vec<u32>, Vec<bingo::BingoBoard>);
+
+impl Parse for Input {
+    fn parse(input: &str) -> Result<Self> {
+        parse_input(
+            input,
+            header(
+                single_line(separated(",", int())),
+                sections(bingo::BingoBoard::from_lines),
+            ),
+        )
+    }
+}
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    const YEAR: u16 = super::YEAR;
+    const DAY: u8 = 4;
+    type Input = Input;
+    const PARTS: &'static [PartFn<Input>] = &[solve_part_1, solve_part_2];
+}
+
+fn solve_part_1((random_numbers, boards): &Input) -> Result<Answer> {
+    first_board_score(with_tracer!(tracer => bingo::find_winning_bingo_boards(
+        random_numbers.clone(),
+        boards.clone(),
+        tracer
+    )))
+}
+
+fn solve_part_2((random_numbers, boards): &Input) -> Result<Answer> {
+    first_board_score(with_tracer!(tracer => bingo::find_losing_bingo_boards(
+        random_numbers.clone(),
+        boards.clone(),
+        tracer
+    )))
+}
+
+fn first_board_score(boards: Vec<bingo::BingoBoard>) -> Result<Answer> {
+    match boards.first() {
+        Some(board) => Ok(board.get_score().into()),
+        None => Err(Box::new(Error::invalid_data("No board got a bingo!"))),
+    }
+}
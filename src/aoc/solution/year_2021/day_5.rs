@@ -0,0 +1,33 @@
+use crate::aoc::solution::{PartFn, Solution};
+use crate::aoc::Answer;
+use crate::common::parsing::{lines, parse_input};
+use crate::common::Parse;
+use crate::sub::pathfinding::{self, LineSegment};
+use crate::Result;
+
+pub type Input = Vec<LineSegment>;
+
+impl Parse for Input {
+    fn parse(input: &str) -> Result<Self> {
+        parse_input(input, lines(LineSegment::parse_line))
+    }
+}
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    const YEAR: u16 = super::YEAR;
+    const DAY: u8 = 5;
+    type Input = Input;
+    const PARTS: &'static [PartFn<Input>] = &[solve_part_1, solve_part_2];
+}
+
+fn solve_part_1(vents: &Input) -> Result<Answer> {
+    let dangerous_point_count = pathfinding::count_dangerous_points(vents, false);
+    Ok(dangerous_point_count.into())
+}
+
+fn solve_part_2(vents: &Input) -> Result<Answer> {
+    let dangerous_point_count = pathfinding::count_dangerous_points(vents, true);
+    Ok(dangerous_point_count.into())
+}
@@ -0,0 +1,39 @@
+use crate::aoc::solution::{PartFn, Solution};
+use crate::aoc::Answer;
+use crate::common::parsing::{int, parse_input, separated, single_line};
+use crate::common::trace::with_tracer;
+use crate::common::Parse;
+use crate::sub::modelling::{simulate_lanternfish, Lanternfish};
+use crate::Result;
+
+pub type Input = Vec<Lanternfish>;
+
+impl Parse for Input {
+    fn parse(input: &str) -> Result<Self> {
+        let timers: Vec<u32> = parse_input(input, single_line(separated(",", int())))?;
+        Ok(timers.into_iter().map(Lanternfish::from).collect())
+    }
+}
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    const YEAR: u16 = super::YEAR;
+    const DAY: u8 = 6;
+    type Input = Input;
+    const PARTS: &'static [PartFn<Input>] = &[solve_part_1, solve_part_2];
+}
+
+fn solve_part_1(initial_fish: &Input) -> Result<Answer> {
+    let days = Day6::param("part_1_days", 80)?;
+    let fish_count =
+        with_tracer!(tracer => simulate_lanternfish(initial_fish.clone(), days, tracer));
+    Ok(fish_count.into())
+}
+
+fn solve_part_2(initial_fish: &Input) -> Result<Answer> {
+    let days = Day6::param("part_2_days", 256)?;
+    let fish_count =
+        with_tracer!(tracer => simulate_lanternfish(initial_fish.clone(), days, tracer));
+    Ok(fish_count.into())
+}
@@ -0,0 +1,33 @@
+use crate::aoc::solution::{PartFn, Solution};
+use crate::aoc::Answer;
+use crate::common::parsing::{int, parse_input, separated, single_line};
+use crate::common::Parse;
+use crate::sub::modelling::{calculate_cheapest_alignment_fuel, FuelModel};
+use crate::Result;
+
+pub type Input = Vec<u32>;
+
+impl Parse for Input {
+    fn parse(input: &str) -> Result<Self> {
+        parse_input(input, single_line(separated(",", int())))
+    }
+}
+
+pub struct Day7;
+
+impl Solution for Day7 {
+    const YEAR: u16 = super::YEAR;
+    const DAY: u8 = 7;
+    type Input = Input;
+    const PARTS: &'static [PartFn<Input>] = &[solve_part_1, solve_part_2];
+}
+
+fn solve_part_1(subs: &Input) -> Result<Answer> {
+    let cheapest_fuel_cost = calculate_cheapest_alignment_fuel(subs.clone(), FuelModel::LINEAR);
+    Ok(cheapest_fuel_cost.into())
+}
+
+fn solve_part_2(subs: &Input) -> Result<Answer> {
+    let cheapest_fuel_cost = calculate_cheapest_alignment_fuel(subs.clone(), FuelModel::TRIANGULAR);
+    Ok(cheapest_fuel_cost.into())
+}
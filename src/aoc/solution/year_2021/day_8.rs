@@ -0,0 +1,44 @@
+use crate::aoc::solution::{PartFn, Solution};
+use crate::aoc::Answer;
+use crate::common::Parse;
+use crate::sub::crypto::seven_segment::{self, OutputDisplay};
+use crate::sub::crypto::{filter_non_unique_digits, unscramble_outputs};
+use crate::Result;
+
+pub type Input = Vec<(Vec<u8>, OutputDisplay)>;
+
+impl Parse for Input {
+    fn parse(input: &str) -> Result<Self> {
+        seven_segment::signal_output_lines_to_signals_and_output(input)
+    }
+}
+
+pub struct Day8;
+
+impl Solution for Day8 {
+    const YEAR: u16 = super::YEAR;
+    const DAY: u8 = 8;
+    type Input = Input;
+    const PARTS: &'static [PartFn<Input>] = &[solve_part_1, solve_part_2];
+}
+
+fn solve_part_1(signals_and_outputs: &Input) -> Result<Answer> {
+    let outputs = outputs_only(signals_and_outputs);
+    let unique_digits = filter_non_unique_digits(&outputs);
+    Ok(unique_digits.len().into())
+}
+
+fn solve_part_2(signals_and_outputs: &Input) -> Result<Answer> {
+    let unscrambled_outputs = unscramble_outputs(signals_and_outputs)?;
+    let output_sum: u32 = unscrambled_outputs
+        .iter()
+        .fold(0, |acc, &output_number| acc + output_number);
+    Ok(output_sum.into())
+}
+
+fn outputs_only(signals_and_outputs: &Input) -> Vec<OutputDisplay> {
+    signals_and_outputs
+        .iter()
+        .map(|(_, output)| output.clone())
+        .collect()
+}
@@ -0,0 +1,36 @@
+use crate::aoc::solution::{PartFn, Solution};
+use crate::aoc::Answer;
+use crate::common::trace::with_tracer;
+use crate::sub::heightmaps::{get_basin_sizes, get_risk_levels, Heightmap};
+use crate::{Error, Result};
+
+pub type Input = Heightmap;
+
+pub struct Day9;
+
+impl Solution for Day9 {
+    const YEAR: u16 = super::YEAR;
+    const DAY: u8 = 9;
+    type Input = Input;
+    const PARTS: &'static [PartFn<Input>] = &[solve_part_1, solve_part_2];
+}
+
+fn solve_part_1(heightmap: &Input) -> Result<Answer> {
+    let risk_levels = get_risk_levels(heightmap);
+    let risk_sum: u32 = risk_levels.values().fold(0, |acc, &risk| acc + risk as u32);
+    Ok(risk_sum.into())
+}
+
+fn solve_part_2(heightmap: &Input) -> Result<Answer> {
+    let mut basin_sizes: Vec<usize> =
+        with_tracer!(tracer => get_basin_sizes(heightmap, true, tracer));
+    basin_sizes.reverse();
+    if basin_sizes.len() < 3 {
+        return Err(Box::new(Error::invalid_data(&format!(
+            "The three largest basins are needed, but there are only {}!",
+            basin_sizes.len()
+        ))));
+    }
+    let basin_product = basin_sizes[..3].iter().fold(1, |acc, size| acc * size);
+    Ok(basin_product.into())
+}
@@ -0,0 +1,120 @@
+mod directory;
+mod json_file;
+mod layered;
+mod memory;
+
+use std::env;
+use std::path::{Path, PathBuf};
+
+use super::challenge::{Challenge, ChallengeKey};
+use crate::{AppParams, Error, Result};
+pub use directory::DirectorySource;
+pub use json_file::JsonFileSource;
+pub use layered::LayeredSource;
+pub use memory::MemorySource;
+
+pub const CHALLENGES_ENV_VAR: &str = "AOC2021_CHALLENGES";
+pub const INPUTS_DIR: &str = "./inputs";
+const REPO_CHALLENGES_FILE: &str = "./data/challenges.json";
+const DATA_DIR_NAME: &str = "aoc2021";
+
+pub trait ChallengeSource {
+    /// Returns the challenge for `key`, or `None` if this source has no entry for it.
+    fn find_challenge(&self, key: ChallengeKey) -> Result<Option<Challenge>>;
+
+    /// Describes where the challenges come from, for error messages.
+    fn location(&self) -> String;
+
+    /// Stores the puzzle data for `key`, adding a challenge for it if there is none yet.
+    fn save_data(&mut self, key: ChallengeKey, _data: String) -> Result<()> {
+        Err(Box::new(Error::new(&format!(
+            "Cannot save the data for challenge {}: {} is read-only!",
+            key,
+            self.location()
+        ))))
+    }
+}
+
+pub fn get_challenge(source: &dyn ChallengeSource, key: ChallengeKey) -> Result<Challenge> {
+    match source.find_challenge(key)? {
+        Some(challenge) => Ok(challenge),
+        None => Err(Box::new(Error::ChallengeNotFound {
+            year: key.year,
+            day: key.day,
+            location: source.location(),
+        })),
+    }
+}
+
+/// Opens the challenge store selected by `--challenges`, then `AOC2021_CHALLENGES`, then the
+/// first store found in the repository or the XDG data directories. Files in `./inputs` are
+/// layered on top of whichever store is used.
+pub fn open_challenge_source(params: &AppParams) -> Result<Box<dyn ChallengeSource>> {
+    let base: Box<dyn ChallengeSource> = match configured_challenges_path(params) {
+        Some(path) => open_path(&path)?,
+        None => match find_challenges_path() {
+            Some(path) => open_path(&path)?,
+            None => Box::new(MemorySource::new()),
+        },
+    };
+    Ok(Box::new(LayeredSource::new(
+        base,
+        DirectorySource::new(PathBuf::from(INPUTS_DIR)),
+    )))
+}
+
+/// The JSON file that `import` writes to: the configured store, or else the first one found.
+pub fn challenges_file_path(params: &AppParams) -> PathBuf {
+    configured_challenges_path(params)
+        .or_else(find_challenges_path)
+        .unwrap_or_else(|| PathBuf::from(REPO_CHALLENGES_FILE))
+}
+
+fn configured_challenges_path(params: &AppParams) -> Option<PathBuf> {
+    params
+        .challenges_path
+        .clone()
+        .or_else(|| env::var_os(CHALLENGES_ENV_VAR).map(PathBuf::from))
+}
+
+fn open_path(path: &Path) -> Result<Box<dyn ChallengeSource>> {
+    if path.is_dir() {
+        Ok(Box::new(DirectorySource::new(path.to_path_buf())))
+    } else {
+        match JsonFileSource::open(path) {
+            Ok(source) => Ok(Box::new(source)),
+            Err(err) => Err(Box::new(Error::io(
+                &format!("Could not read challenges from {}!", path.display()),
+                err,
+            ))),
+        }
+    }
+}
+
+fn find_challenges_path() -> Option<PathBuf> {
+    let mut candidates = vec![PathBuf::from(REPO_CHALLENGES_FILE)];
+    for data_dir in xdg_data_dirs() {
+        let app_dir = data_dir.join(DATA_DIR_NAME);
+        candidates.push(app_dir.join("challenges.json"));
+        candidates.push(app_dir.join("inputs"));
+    }
+    candidates.into_iter().find(|path| path.exists())
+}
+
+fn xdg_data_dirs() -> Vec<PathBuf> {
+    let mut dirs: Vec<PathBuf> = vec![];
+    match env::var_os("XDG_DATA_HOME").filter(|dir| !dir.is_empty()) {
+        Some(data_home) => dirs.push(PathBuf::from(data_home)),
+        None => {
+            if let Some(home) = env::var_os("HOME") {
+                dirs.push(Path::new(&home).join(".local/share"));
+            }
+        }
+    }
+    let data_dirs = env::var("XDG_DATA_DIRS")
+        .ok()
+        .filter(|dirs| !dirs.is_empty())
+        .unwrap_or_else(|| String::from("/usr/local/share:/usr/share"));
+    dirs.extend(data_dirs.split(':').map(PathBuf::from));
+    dirs
+}
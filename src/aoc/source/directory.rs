@@ -0,0 +1,135 @@
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+use super::ChallengeSource;
+use crate::aoc::challenge::{Challenge, ChallengeKey, Example, DEFAULT_EXAMPLE_NAME};
+use crate::aoc::solution::Dataset;
+use crate::cli::DEFAULT_YEAR;
+use crate::Result;
+
+/// A directory of plain-text inputs laid out as `2021/day_05.txt` and `2021/day_05.example.txt`.
+/// Inputs for the default year may also sit directly in the directory, as they did before
+/// challenges had years.
+pub struct DirectorySource {
+    root: PathBuf,
+}
+
+impl DirectorySource {
+    pub fn new(root: PathBuf) -> DirectorySource {
+        DirectorySource { root }
+    }
+
+    pub fn day_input_path(&self, key: ChallengeKey, dataset: Dataset) -> PathBuf {
+        self.root
+            .join(key.year.to_string())
+            .join(day_input_file_name(key.day, dataset))
+    }
+
+    pub fn read_day_input(&self, key: ChallengeKey, dataset: Dataset) -> Result<Option<String>> {
+        if let Some(data) = read_if_exists(&self.day_input_path(key, dataset))? {
+            return Ok(Some(data));
+        }
+        if key.year == DEFAULT_YEAR {
+            return read_if_exists(&self.root.join(day_input_file_name(key.day, dataset)));
+        }
+        Ok(None)
+    }
+}
+
+fn day_input_file_name(day: u8, dataset: Dataset) -> String {
+    match dataset {
+        Dataset::Example(_) => format!("day_{:02}.example.txt", day),
+        Dataset::Real => format!("day_{:02}.txt", day),
+    }
+}
+
+fn read_if_exists(path: &Path) -> Result<Option<String>> {
+    match fs::read_to_string(path) {
+        Ok(data) => Ok(Some(data)),
+        Err(err) if err.kind() == ErrorKind::NotFound => Ok(None),
+        Err(err) => Err(Box::new(err)),
+    }
+}
+
+impl ChallengeSource for DirectorySource {
+    fn find_challenge(&self, key: ChallengeKey) -> Result<Option<Challenge>> {
+        let example_data = self.read_day_input(key, Dataset::Example(0))?;
+        let data = self.read_day_input(key, Dataset::Real)?;
+        if example_data.is_none() && data.is_none() {
+            return Ok(None);
+        }
+        Ok(Some(Challenge {
+            examples: example_data
+                .map(|data| vec![Example::new(DEFAULT_EXAMPLE_NAME, data)])
+                .unwrap_or_default(),
+            data: data.unwrap_or_default(),
+            ..Challenge::new(key)
+        }))
+    }
+
+    fn location(&self) -> String {
+        self.root.display().to_string()
+    }
+
+    fn save_data(&mut self, key: ChallengeKey, data: String) -> Result<()> {
+        let path = self.day_input_path(key, Dataset::Real);
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, data)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aoc::test_dir::TestDir;
+
+    #[test]
+    fn day_input_paths_are_zero_padded_within_years() {
+        let source = DirectorySource::new(PathBuf::from("inputs"));
+        assert_eq!(
+            Path::new("inputs/2021/day_05.txt"),
+            source.day_input_path(ChallengeKey::new(2021, 5), Dataset::Real)
+        );
+        assert_eq!(
+            Path::new("inputs/2022/day_12.example.txt"),
+            source.day_input_path(ChallengeKey::new(2022, 12), Dataset::Example(0))
+        );
+    }
+
+    #[test]
+    fn days_without_files_are_not_found() -> Result<()> {
+        let dir = TestDir::new("missing_inputs");
+        let source = DirectorySource::new(dir.join("inputs"));
+        assert!(source.find_challenge(ChallengeKey::new(2021, 5))?.is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn day_inputs_are_read_per_dataset() -> Result<()> {
+        let dir = TestDir::new("day_inputs");
+        let mut source = DirectorySource::new(dir.to_path_buf());
+        let key = ChallengeKey::new(2022, 7);
+        source.save_data(key, String::from("16,1,2,0"))?;
+        fs::write(source.day_input_path(key, Dataset::Example(0)), "16,1,2")?;
+        let challenge = source.find_challenge(key)?.unwrap();
+        assert_eq!("16,1,2", challenge.data_for(Dataset::Example(0)));
+        assert_eq!("16,1,2,0", challenge.data);
+        assert_eq!(2022, challenge.year);
+        Ok(())
+    }
+
+    #[test]
+    fn default_year_inputs_can_be_outside_a_year_directory() -> Result<()> {
+        let dir = TestDir::new("legacy_inputs");
+        let source = DirectorySource::new(dir.to_path_buf());
+        fs::write(dir.join("day_03.txt"), "10110")?;
+        let challenge = source.find_challenge(ChallengeKey::new(DEFAULT_YEAR, 3))?;
+        assert_eq!("10110", challenge.unwrap().data);
+        assert!(source.find_challenge(ChallengeKey::new(2022, 3))?.is_none());
+        Ok(())
+    }
+}
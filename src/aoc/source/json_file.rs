@@ -0,0 +1,103 @@
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use serde_json::{Map, Value};
+
+use super::ChallengeSource;
+use crate::aoc::challenge::{Challenge, ChallengeKey};
+use crate::aoc::import::{import_challenge, nest_by_year, write_challenges, ImportedChallenge};
+use crate::{Error, Result};
+
+/// A `challenges.json` file, keyed by year and then by day. The file is parsed once, when opened.
+pub struct JsonFileSource {
+    path: PathBuf,
+    challenges: HashMap<ChallengeKey, Challenge>,
+}
+
+impl JsonFileSource {
+    pub fn open(path: &Path) -> Result<JsonFileSource> {
+        Ok(JsonFileSource {
+            path: path.to_path_buf(),
+            challenges: read_challenges(&read_file(path)?)?,
+        })
+    }
+}
+
+fn read_file(path: &Path) -> Result<Map<String, Value>> {
+    let mut challenges_str = String::new();
+    File::open(path)?.read_to_string(&mut challenges_str)?;
+    Ok(nest_by_year(serde_json::from_str(&challenges_str)?))
+}
+
+fn read_challenges(challenges: &Map<String, Value>) -> Result<HashMap<ChallengeKey, Challenge>> {
+    let mut by_key: HashMap<ChallengeKey, Challenge> = HashMap::new();
+    for (year_str, days) in challenges {
+        let year = u16::from_str(year_str)
+            .map_err(|err| Error::parse_caused_by(&format!("Invalid year {}!", year_str), err))?;
+        let days: HashMap<String, Challenge> = serde_json::from_value(days.clone())?;
+        for (day_str, challenge) in days {
+            let day = u8::from_str(&day_str).map_err(|err| {
+                Error::parse_caused_by(&format!("Invalid day {} in {}!", day_str, year), err)
+            })?;
+            let key = ChallengeKey::new(year, day);
+            by_key.insert(
+                key,
+                Challenge {
+                    year,
+                    day: if challenge.day == 0 {
+                        day
+                    } else {
+                        challenge.day
+                    },
+                    ..challenge
+                },
+            );
+        }
+    }
+    Ok(by_key)
+}
+
+impl ChallengeSource for JsonFileSource {
+    fn find_challenge(&self, key: ChallengeKey) -> Result<Option<Challenge>> {
+        Ok(self.challenges.get(&key).cloned())
+    }
+
+    fn location(&self) -> String {
+        self.path.display().to_string()
+    }
+
+    fn save_data(&mut self, key: ChallengeKey, data: String) -> Result<()> {
+        let mut challenges = read_file(&self.path)?;
+        let imported = ImportedChallenge {
+            key,
+            parts: None,
+            examples: vec![],
+            data: Some(data),
+        };
+        import_challenge(&mut challenges, imported, true)?;
+        write_challenges(&self.path, &challenges)?;
+        self.challenges = read_challenges(&challenges)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn challenges_are_keyed_by_year_and_day() -> Result<()> {
+        let challenges = read_challenges(&serde_json::from_str(
+            r#"{"2021": {"1": {"data": "199"}}, "2022": {"1": {"data": "1000"}}}"#,
+        )?)?;
+        let challenge = &challenges[&ChallengeKey::new(2022, 1)];
+        assert_eq!((2022, 1), (challenge.year, challenge.day));
+        assert_eq!("1000", challenge.data);
+        assert_eq!("199", challenges[&ChallengeKey::new(2021, 1)].data);
+        assert!(read_challenges(&serde_json::from_str(r#"{"next": {}}"#)?).is_err());
+        Ok(())
+    }
+}
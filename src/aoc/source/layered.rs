@@ -0,0 +1,67 @@
+use super::{ChallengeSource, DirectorySource};
+use crate::aoc::challenge::{Challenge, ChallengeKey};
+use crate::aoc::solution::Dataset;
+use crate::Result;
+
+/// A base store whose puzzle data can be replaced by files from an inputs directory.
+/// Saved data always goes to the inputs directory, leaving the base store untouched.
+pub struct LayeredSource {
+    base: Box<dyn ChallengeSource>,
+    inputs: DirectorySource,
+}
+
+impl LayeredSource {
+    pub fn new(base: Box<dyn ChallengeSource>, inputs: DirectorySource) -> LayeredSource {
+        LayeredSource { base, inputs }
+    }
+}
+
+impl ChallengeSource for LayeredSource {
+    fn find_challenge(&self, key: ChallengeKey) -> Result<Option<Challenge>> {
+        let mut challenge = match self.base.find_challenge(key)? {
+            Some(challenge) => challenge,
+            None => return self.inputs.find_challenge(key),
+        };
+        for dataset in [Dataset::Example(0), Dataset::Real] {
+            if let Some(data) = self.inputs.read_day_input(key, dataset)? {
+                challenge.set_data_for(dataset, data);
+            }
+        }
+        Ok(Some(challenge))
+    }
+
+    fn location(&self) -> String {
+        format!("{} or {}", self.base.location(), self.inputs.location())
+    }
+
+    fn save_data(&mut self, key: ChallengeKey, data: String) -> Result<()> {
+        self.inputs.save_data(key, data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aoc::challenge::Example;
+    use crate::aoc::source::MemorySource;
+    use crate::aoc::test_dir::TestDir;
+
+    #[test]
+    fn input_files_replace_base_data() -> Result<()> {
+        let dir = TestDir::new("layered_inputs");
+        let mut inputs = DirectorySource::new(dir.to_path_buf());
+        let key = ChallengeKey::new(2021, 3);
+        inputs.save_data(key, String::from("10110"))?;
+        let base = MemorySource::new().with_challenge(Challenge {
+            examples: vec![Example::new("example", String::from("00100"))],
+            data: String::from("11111"),
+            ..Challenge::new(key)
+        });
+        let challenge = LayeredSource::new(Box::new(base), inputs)
+            .find_challenge(key)?
+            .unwrap();
+        assert_eq!("00100", challenge.data_for(Dataset::Example(0)));
+        assert_eq!("10110", challenge.data);
+        Ok(())
+    }
+}
@@ -0,0 +1,41 @@
+use std::collections::BTreeMap;
+
+use super::ChallengeSource;
+use crate::aoc::challenge::{Challenge, ChallengeKey};
+use crate::Result;
+
+/// Challenges held in memory, mainly for tests.
+#[derive(Default)]
+pub struct MemorySource {
+    challenges: BTreeMap<ChallengeKey, Challenge>,
+}
+
+impl MemorySource {
+    pub fn new() -> MemorySource {
+        MemorySource::default()
+    }
+
+    #[cfg(test)]
+    pub fn with_challenge(mut self, challenge: Challenge) -> MemorySource {
+        self.challenges.insert(challenge.key(), challenge);
+        self
+    }
+}
+
+impl ChallengeSource for MemorySource {
+    fn find_challenge(&self, key: ChallengeKey) -> Result<Option<Challenge>> {
+        Ok(self.challenges.get(&key).cloned())
+    }
+
+    fn location(&self) -> String {
+        String::from("memory")
+    }
+
+    fn save_data(&mut self, key: ChallengeKey, data: String) -> Result<()> {
+        self.challenges
+            .entry(key)
+            .or_insert_with(|| Challenge::new(key))
+            .data = data;
+        Ok(())
+    }
+}
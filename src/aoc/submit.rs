@@ -0,0 +1,413 @@
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use super::challenge::ChallengeKey;
+use super::client::AocClient;
+use super::solution::{solution_registry, Dataset};
+use super::source::{challenges_file_path, get_challenge, ChallengeSource};
+use crate::cli::DEFAULT_YEAR;
+use crate::common::SubmitParams;
+use crate::{AppParams, Error, Result};
+
+const SUBMISSIONS_FILE_NAME: &str = "submissions.json";
+/// How long the server makes you wait after a wrong answer when it doesn't say otherwise.
+const WRONG_ANSWER_COOLDOWN_SECS: u64 = 60;
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SubmitOutcome {
+    Correct,
+    TooHigh,
+    TooLow,
+    Wrong,
+    Wait(u64),
+    AlreadySolved,
+    Unknown,
+}
+
+impl Display for SubmitOutcome {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SubmitOutcome::Correct => write!(f, "That's the right answer!"),
+            SubmitOutcome::TooHigh => write!(f, "That's not the right answer; it is too high."),
+            SubmitOutcome::TooLow => write!(f, "That's not the right answer; it is too low."),
+            SubmitOutcome::Wrong => write!(f, "That's not the right answer."),
+            SubmitOutcome::Wait(secs) => {
+                write!(
+                    f,
+                    "Answered too recently; wait {}s before trying again.",
+                    secs
+                )
+            }
+            SubmitOutcome::AlreadySolved => write!(f, "This part has already been solved."),
+            SubmitOutcome::Unknown => write!(f, "The server's response was not recognised."),
+        }
+    }
+}
+
+/// Reads the outcome of a submission from the server's response page.
+pub fn parse_submit_response(page: &str) -> SubmitOutcome {
+    if page.contains("That's the right answer") {
+        SubmitOutcome::Correct
+    } else if page.contains("That's not the right answer") {
+        if page.contains("too high") {
+            SubmitOutcome::TooHigh
+        } else if page.contains("too low") {
+            SubmitOutcome::TooLow
+        } else {
+            SubmitOutcome::Wrong
+        }
+    } else if page.contains("You gave an answer too recently") {
+        SubmitOutcome::Wait(parse_wait_secs(page).unwrap_or(WRONG_ANSWER_COOLDOWN_SECS))
+    } else if page.contains("Did you already complete it") {
+        SubmitOutcome::AlreadySolved
+    } else {
+        SubmitOutcome::Unknown
+    }
+}
+
+/// Parses "You have 1m 23s left to wait" into seconds.
+fn parse_wait_secs(page: &str) -> Option<u64> {
+    let start = page.find("You have ")? + "You have ".len();
+    let end = start + page[start..].find(" left to wait")?;
+    let mut secs = 0;
+    for token in page[start..end].split_whitespace() {
+        secs += match token.strip_suffix('m') {
+            Some(minutes) => minutes.parse::<u64>().ok()? * 60,
+            None => token.strip_suffix('s')?.parse::<u64>().ok()?,
+        };
+    }
+    Some(secs)
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct Attempt {
+    /// Attempts recorded before multi-year support have no year; they were all for 2021.
+    #[serde(default = "default_year")]
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+    pub answer: String,
+    pub outcome: SubmitOutcome,
+    pub submitted_at: u64,
+}
+
+fn default_year() -> u16 {
+    DEFAULT_YEAR
+}
+
+impl Attempt {
+    fn key(&self) -> ChallengeKey {
+        ChallengeKey::new(self.year, self.day)
+    }
+
+    /// When the server will accept the next answer after this attempt.
+    fn cooldown_until(&self) -> u64 {
+        match self.outcome {
+            SubmitOutcome::Wait(secs) => self.submitted_at + secs,
+            SubmitOutcome::TooHigh | SubmitOutcome::TooLow | SubmitOutcome::Wrong => {
+                self.submitted_at + WRONG_ANSWER_COOLDOWN_SECS
+            }
+            _ => self.submitted_at,
+        }
+    }
+}
+
+/// Every answer submitted so far, used to avoid sending answers that cannot be right.
+#[derive(Default, Deserialize, Serialize)]
+pub struct SubmissionHistory {
+    pub attempts: Vec<Attempt>,
+}
+
+impl SubmissionHistory {
+    pub fn load(path: &Path) -> Result<SubmissionHistory> {
+        match fs::read_to_string(path) {
+            Ok(history_str) => Ok(serde_json::from_str(&history_str)?),
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(SubmissionHistory::default()),
+            Err(err) => Err(Box::new(err)),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Fails if `answer` is already known to be wrong, lies outside the bounds learned from
+    /// earlier "too high" and "too low" responses, or if the server is still cooling down.
+    pub fn check(&self, key: ChallengeKey, part: u8, answer: &str, now: u64) -> Result<()> {
+        let attempts: Vec<&Attempt> = self
+            .attempts
+            .iter()
+            .filter(|attempt| attempt.key() == key && attempt.part == part)
+            .collect();
+        let reject = |message: String| -> Result<()> { Err(Box::new(Error::new(&message))) };
+
+        if let Some(correct) = attempts
+            .iter()
+            .find(|attempt| attempt.outcome == SubmitOutcome::Correct)
+        {
+            return reject(format!(
+                "{} part {} was already solved with {}!",
+                key, part, correct.answer
+            ));
+        }
+        if let Some(previous) = attempts.iter().find(|attempt| {
+            attempt.answer == answer
+                && matches!(
+                    attempt.outcome,
+                    SubmitOutcome::TooHigh | SubmitOutcome::TooLow | SubmitOutcome::Wrong
+                )
+        }) {
+            return reject(format!(
+                "{} was already rejected: {}",
+                answer, previous.outcome
+            ));
+        }
+        if let Ok(value) = answer.parse::<i64>() {
+            let bound = |outcome: SubmitOutcome| {
+                attempts
+                    .iter()
+                    .filter(move |attempt| attempt.outcome == outcome)
+                    .filter_map(|attempt| attempt.answer.parse::<i64>().ok())
+            };
+            if let Some(high) = bound(SubmitOutcome::TooHigh).min() {
+                if value >= high {
+                    return reject(format!(
+                        "{} is not below {}, which was too high!",
+                        value, high
+                    ));
+                }
+            }
+            if let Some(low) = bound(SubmitOutcome::TooLow).max() {
+                if value <= low {
+                    return reject(format!(
+                        "{} is not above {}, which was too low!",
+                        value, low
+                    ));
+                }
+            }
+        }
+        let cooldown_until = self
+            .attempts
+            .iter()
+            .filter(|attempt| attempt.key() == key)
+            .map(|attempt| attempt.cooldown_until())
+            .max()
+            .unwrap_or(0);
+        if cooldown_until > now {
+            return reject(format!(
+                "Answered too recently; wait {}s before trying again.",
+                cooldown_until - now
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Checks `answer` against the history, submits it and records the attempt.
+pub fn submit_answer(
+    client: &AocClient,
+    history: &mut SubmissionHistory,
+    key: ChallengeKey,
+    part: u8,
+    answer: &str,
+    now: u64,
+) -> Result<SubmitOutcome> {
+    history.check(key, part, answer, now)?;
+    let outcome = parse_submit_response(&client.submit_answer(key, part, answer)?);
+    history.attempts.push(Attempt {
+        year: key.year,
+        day: key.day,
+        part,
+        answer: answer.to_string(),
+        outcome,
+        submitted_at: now,
+    });
+    Ok(outcome)
+}
+
+/// The history is kept with the challenges it is for: in the inputs directory they are read from,
+/// or beside their challenges.json.
+pub fn submissions_file_path(params: &AppParams) -> PathBuf {
+    let store = challenges_file_path(params);
+    let dir = if store.is_dir() {
+        store.as_path()
+    } else {
+        store.parent().unwrap_or(Path::new("."))
+    };
+    dir.join(SUBMISSIONS_FILE_NAME)
+}
+
+pub fn run_submit(
+    source: &dyn ChallengeSource,
+    params: &AppParams,
+    submit: &SubmitParams,
+) -> Result<()> {
+    let key = ChallengeKey::new(params.year, submit.day as u8);
+    let part = submit.part as u8;
+    let answer = match &submit.answer {
+        Some(answer) => answer.clone(),
+        None => {
+            let challenge = get_challenge(source, key)?;
+            let solution = match solution_registry()?.get_part(key, part) {
+                Some(solution) => solution,
+                None => {
+                    return Err(Box::new(Error::SolutionNotFound {
+                        year: key.year,
+                        day: key.day,
+                        part,
+                    }))
+                }
+            };
+            solution
+                .run_on(&challenge, Dataset::Real, part)?
+                .to_string()
+                .trim()
+                .to_string()
+        }
+    };
+    let client = AocClient::from_params(&submit.server)?;
+    let history_path = submissions_file_path(params);
+    let mut history = SubmissionHistory::load(&history_path)?;
+    let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+    println!("Submitting {} for {} part {}...", answer, key, part);
+    let outcome = submit_answer(&client, &mut history, key, part, &answer, now);
+    history.save(&history_path)?;
+    println!("{}", outcome?);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::Ordering;
+
+    use super::*;
+    use crate::aoc::test_dir::TestDir;
+    use crate::aoc::test_server;
+    use crate::cli::parse_args;
+    use crate::config::Config;
+
+    const DAY_1: ChallengeKey = ChallengeKey { year: 2021, day: 1 };
+    const TOO_HIGH_PAGE: &str = "<article><p>That's not the right answer; your answer is too high.  If you're stuck, make sure you're using the full input data. Please wait one minute before trying again.</p></article>";
+    const RIGHT_PAGE: &str = "<article><p>That's the right answer!  You are one gold star closer to saving your vacation.</p></article>";
+    const WAIT_PAGE: &str = "<article><p>You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 23s left to wait.</p></article>";
+
+    fn attempt(answer: &str, outcome: SubmitOutcome) -> Attempt {
+        Attempt {
+            year: 2021,
+            day: 1,
+            part: 1,
+            answer: String::from(answer),
+            outcome,
+            submitted_at: 0,
+        }
+    }
+
+    #[test]
+    fn responses_are_parsed() {
+        assert_eq!(SubmitOutcome::TooHigh, parse_submit_response(TOO_HIGH_PAGE));
+        assert_eq!(SubmitOutcome::Correct, parse_submit_response(RIGHT_PAGE));
+        assert_eq!(SubmitOutcome::Wait(83), parse_submit_response(WAIT_PAGE));
+        assert_eq!(
+            SubmitOutcome::Unknown,
+            parse_submit_response("<html></html>")
+        );
+    }
+
+    #[test]
+    fn known_wrong_answers_and_bounds_are_rejected() {
+        let history = SubmissionHistory {
+            attempts: vec![
+                attempt("500", SubmitOutcome::TooHigh),
+                attempt("100", SubmitOutcome::TooLow),
+                attempt("abc", SubmitOutcome::Wrong),
+            ],
+        };
+        assert!(history.check(DAY_1, 1, "abc", 1000).is_err());
+        assert!(history.check(DAY_1, 1, "500", 1000).is_err());
+        assert!(history.check(DAY_1, 1, "600", 1000).is_err());
+        assert!(history.check(DAY_1, 1, "100", 1000).is_err());
+        assert!(history.check(DAY_1, 1, "99", 1000).is_err());
+        assert!(history.check(DAY_1, 1, "250", 1000).is_ok());
+        assert!(history.check(DAY_1, 2, "600", 1000).is_ok());
+        assert!(history
+            .check(ChallengeKey::new(2022, 1), 1, "600", 1000)
+            .is_ok());
+    }
+
+    #[test]
+    fn answers_are_rejected_during_cooldown() {
+        let history = SubmissionHistory {
+            attempts: vec![attempt("500", SubmitOutcome::TooHigh)],
+        };
+        assert!(history.check(DAY_1, 1, "250", 30).is_err());
+        assert!(history.check(DAY_1, 1, "250", 60).is_ok());
+    }
+
+    #[test]
+    fn submissions_are_sent_and_recorded() -> Result<()> {
+        let (base_url, requests) = test_server::start(|request| {
+            if request.method == "POST"
+                && request.path == "/2021/day/1/answer"
+                && request.body == "level=1&answer=1655"
+            {
+                (200, String::from(RIGHT_PAGE))
+            } else {
+                (200, String::from(TOO_HIGH_PAGE))
+            }
+        });
+        let client = AocClient::new(&base_url, "secret");
+        let mut history = SubmissionHistory::default();
+
+        let outcome = submit_answer(&client, &mut history, DAY_1, 1, "2000", 0)?;
+        assert_eq!(SubmitOutcome::TooHigh, outcome);
+        assert!(submit_answer(&client, &mut history, DAY_1, 1, "3000", 100).is_err());
+        let outcome = submit_answer(&client, &mut history, DAY_1, 1, "1655", 100)?;
+        assert_eq!(SubmitOutcome::Correct, outcome);
+
+        assert_eq!(2, requests.load(Ordering::SeqCst));
+        assert_eq!(2, history.attempts.len());
+        Ok(())
+    }
+
+    #[test]
+    fn history_is_kept_beside_the_challenges() -> Result<()> {
+        let dir = TestDir::new("submissions");
+        let history_path = |challenges: &Path| -> Result<PathBuf> {
+            let line = format!(
+                "aoc2021 submit --day 1 --part 1 --challenges {}",
+                challenges.display()
+            );
+            let params = parse_args(
+                line.split_whitespace().map(String::from),
+                &Config::default(),
+            )?;
+            Ok(submissions_file_path(&params))
+        };
+        let private = dir.join("private");
+        assert_eq!(
+            private.join(SUBMISSIONS_FILE_NAME),
+            history_path(&private.join("challenges.json"))?
+        );
+        fs::create_dir_all(dir.join("inputs"))?;
+        let path = history_path(&dir.join("inputs"))?;
+        assert_eq!(dir.join("inputs").join(SUBMISSIONS_FILE_NAME), path);
+
+        let history = SubmissionHistory {
+            attempts: vec![attempt("500", SubmitOutcome::TooHigh)],
+        };
+        let new_path = private.join(SUBMISSIONS_FILE_NAME);
+        history.save(&new_path)?;
+        assert_eq!(1, SubmissionHistory::load(&new_path)?.attempts.len());
+        Ok(())
+    }
+}
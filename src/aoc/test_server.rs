@@ -0,0 +1,73 @@
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+
+pub struct Request {
+    pub method: String,
+    pub path: String,
+    pub cookie: Option<String>,
+    pub body: String,
+}
+
+/// A stand-in for the puzzle server. Every request is answered by `respond`, which returns a
+/// status code and body. Returns the base URL to point clients at and a request counter.
+pub fn start<F>(respond: F) -> (String, Arc<AtomicUsize>)
+where
+    F: Fn(&Request) -> (u16, String) + Send + 'static,
+{
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let base_url = format!("http://{}", listener.local_addr().unwrap());
+    let requests = Arc::new(AtomicUsize::new(0));
+    let counter = requests.clone();
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = stream.unwrap();
+            let request = read_request(&mut BufReader::new(stream.try_clone().unwrap()));
+            counter.fetch_add(1, Ordering::SeqCst);
+            let (status, body) = respond(&request);
+            let response = format!(
+                "HTTP/1.1 {} Stub\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                status,
+                body.len(),
+                body
+            );
+            stream.write_all(response.as_bytes()).unwrap();
+        }
+    });
+    (base_url, requests)
+}
+
+fn read_request<R: Read>(reader: &mut BufReader<R>) -> Request {
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line).unwrap();
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or_default().to_string();
+    let path = parts.next().unwrap_or_default().to_string();
+    let mut cookie: Option<String> = None;
+    let mut content_length = 0;
+    loop {
+        let mut header = String::new();
+        reader.read_line(&mut header).unwrap();
+        let header = header.trim();
+        if header.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(": ") {
+            match name.to_ascii_lowercase().as_str() {
+                "cookie" => cookie = Some(value.to_string()),
+                "content-length" => content_length = value.parse().unwrap(),
+                _ => {}
+            }
+        }
+    }
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body).unwrap();
+    Request {
+        method,
+        path,
+        cookie,
+        body: String::from_utf8(body).unwrap(),
+    }
+}
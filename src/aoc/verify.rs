@@ -0,0 +1,235 @@
+use std::fmt::{Display, Formatter};
+use std::ops::RangeInclusive;
+
+use super::challenge::{Challenge, ChallengeKey};
+use super::solution::{solution_registry, ChallengeSolution, Dataset, Registry};
+use super::source::{get_challenge, ChallengeSource};
+use crate::common::describe_error;
+use crate::common::jobs::run_in_order;
+use crate::{Error, Result};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum VerifyStatus {
+    Pass,
+    Fail,
+    Unknown,
+}
+
+impl Display for VerifyStatus {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            VerifyStatus::Pass => f.pad("pass"),
+            VerifyStatus::Fail => f.pad("FAIL"),
+            VerifyStatus::Unknown => f.pad("unknown"),
+        }
+    }
+}
+
+pub struct VerifyResult {
+    pub day: u8,
+    pub part: u8,
+    pub dataset: String,
+    pub status: VerifyStatus,
+    pub actual: String,
+    pub expected: Option<String>,
+}
+
+/// A day's solution to check against one dataset, or the reason the day's challenge could not be
+/// loaded.
+enum VerifyTask<'a> {
+    Check {
+        parts: Vec<u8>,
+        solution: ChallengeSolution,
+        challenge: &'a Challenge,
+        dataset: Dataset,
+    },
+    LoadFailed {
+        day: u8,
+        message: String,
+    },
+}
+
+/// Loads the challenges one at a time, then checks every dataset on `jobs` threads. Results are
+/// in day, part and dataset order either way.
+pub fn run_verification(
+    source: &dyn ChallengeSource,
+    solutions: &Registry,
+    year: u16,
+    days: RangeInclusive<u8>,
+    only_part: Option<u8>,
+    jobs: usize,
+) -> Vec<VerifyResult> {
+    let loaded: Vec<(ChallengeKey, ChallengeSolution, Result<Challenge>)> = days
+        .map(|day| ChallengeKey::new(year, day))
+        .filter_map(|key| solutions.get(key).map(|solution| (key, solution)))
+        .map(|(key, solution)| (key, solution, get_challenge(source, key)))
+        .collect();
+    let mut tasks: Vec<VerifyTask> = vec![];
+    for (key, solution, challenge) in &loaded {
+        let challenge = match challenge {
+            Ok(challenge) => challenge,
+            Err(err) => {
+                tasks.push(VerifyTask::LoadFailed {
+                    day: key.day,
+                    message: describe_error(err.as_ref()),
+                });
+                continue;
+            }
+        };
+        let parts: Vec<u8> = (1..=challenge.parts)
+            .filter(|&part| only_part.is_none_or(|p| p == part))
+            .filter(|&part| solution.has_part(part))
+            .collect();
+        for dataset in challenge.datasets() {
+            tasks.push(VerifyTask::Check {
+                parts: parts.clone(),
+                solution: *solution,
+                challenge,
+                dataset,
+            });
+        }
+    }
+    let mut results: Vec<VerifyResult> = run_in_order(&tasks, jobs, run_task)
+        .into_iter()
+        .flatten()
+        .collect();
+    // Each task covers one dataset, so bring each day's parts together. The sort is stable, so
+    // the datasets stay in order within each part.
+    results.sort_by_key(|result| (result.day, result.part));
+    results
+}
+
+/// Parses the dataset once and checks every part's answer from it.
+fn run_task(task: &VerifyTask) -> Vec<VerifyResult> {
+    match task {
+        VerifyTask::Check {
+            parts,
+            solution,
+            challenge,
+            dataset,
+        } => {
+            let input = solution.parse(challenge.data_for(*dataset));
+            parts
+                .iter()
+                .map(|&part| {
+                    let expected = challenge
+                        .expected_answer(*dataset, part)
+                        .map(|answer| answer.trim().to_string());
+                    let answer = match &input {
+                        Ok(input) => solution
+                            .solve(part, input)
+                            .map_err(|err| describe_error(err.as_ref())),
+                        Err(err) => Err(describe_error(err.as_ref())),
+                    };
+                    let (status, actual) = match answer {
+                        Ok(answer) => {
+                            let actual = answer.to_string().trim().to_string();
+                            let status = match &expected {
+                                Some(expected) if *expected == actual => VerifyStatus::Pass,
+                                Some(_) => VerifyStatus::Fail,
+                                None => VerifyStatus::Unknown,
+                            };
+                            (status, actual)
+                        }
+                        Err(message) => (VerifyStatus::Fail, format!("error: {}", message)),
+                    };
+                    VerifyResult {
+                        day: challenge.day,
+                        part,
+                        dataset: challenge.dataset_name(*dataset).to_string(),
+                        status,
+                        actual,
+                        expected,
+                    }
+                })
+                .collect()
+        }
+        VerifyTask::LoadFailed { day, message } => vec![VerifyResult {
+            day: *day,
+            part: 1,
+            dataset: String::from("real"),
+            status: VerifyStatus::Fail,
+            actual: format!("error: {}", message),
+            expected: None,
+        }],
+    }
+}
+
+pub fn run_verification_with_summary(
+    source: &dyn ChallengeSource,
+    year: u16,
+    days: RangeInclusive<u8>,
+    only_part: Option<u8>,
+    jobs: usize,
+) -> Result<()> {
+    let results = run_verification(source, solution_registry()?, year, days, only_part, jobs);
+    print_verification_summary(&results);
+    let failures = count_status(&results, VerifyStatus::Fail);
+    println!(
+        "\n{} passed, {} failed, {} unknown.",
+        count_status(&results, VerifyStatus::Pass),
+        failures,
+        count_status(&results, VerifyStatus::Unknown)
+    );
+    if failures > 0 {
+        Err(Box::new(Error::new(&format!(
+            "{} of {} verifications failed!",
+            failures,
+            results.len()
+        ))))
+    } else {
+        Ok(())
+    }
+}
+
+pub fn print_verification_summary(results: &[VerifyResult]) {
+    println!(" Day | Part | Dataset | Result  | Answer");
+    println!("-----+------+---------+---------+--------");
+    for result in results {
+        println!(
+            " {:>3} | {:>4} | {:<7} | {:<7} | {}",
+            result.day,
+            result.part,
+            result.dataset,
+            result.status,
+            match (&result.status, &result.expected) {
+                (VerifyStatus::Fail, Some(expected)) => {
+                    format!("{} (expected {})", result.actual, expected)
+                }
+                _ => result.actual.replace('\n', "\\n"),
+            }
+        );
+    }
+}
+
+fn count_status(results: &[VerifyResult], status: VerifyStatus) -> usize {
+    results
+        .iter()
+        .filter(|result| result.status == status)
+        .count()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::*;
+    use crate::aoc::challenge::Example;
+    use crate::aoc::source::MemorySource;
+
+    #[test]
+    fn verification_compares_answers_from_the_source() -> Result<()> {
+        let source = MemorySource::new().with_challenge(Challenge {
+            examples: vec![Example {
+                answers: BTreeMap::from([(1, String::from("37")), (2, String::from("1"))]),
+                ..Example::new("example", String::from("16,1,2,0,4,2,7,1,2,14"))
+            }],
+            ..Challenge::new(ChallengeKey::new(2021, 7))
+        });
+        let results = run_verification(&source, solution_registry()?, 2021, 7..=7, None, 2);
+        let statuses: Vec<VerifyStatus> = results.iter().map(|result| result.status).collect();
+        assert_eq!(vec![VerifyStatus::Pass, VerifyStatus::Fail], statuses);
+        assert_eq!("168", results[1].actual);
+        Ok(())
+    }
+}
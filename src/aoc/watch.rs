@@ -0,0 +1,337 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use serde::Deserialize;
+use serde_json::Value;
+
+use super::challenge::ChallengeKey;
+use super::source::{challenges_file_path, INPUTS_DIR};
+use crate::common::{describe_error, InputSource, WatchParams};
+use crate::config::CONFIG_FILE_NAMES;
+use crate::{AppParams, Error, Result};
+
+/// The code the solutions are built from, relative to the root of the repository.
+const WATCHED_SOURCES: [&str; 4] = [
+    "./src/aoc/solution.rs",
+    "./src/aoc/solution",
+    "./src/sub.rs",
+    "./src/sub",
+];
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+/// How long to wait after a change for the rest of an editor's writes to land.
+const SETTLE_TIME: Duration = Duration::from_millis(200);
+
+/// The modification time and size of every watched file.
+type Snapshot = BTreeMap<PathBuf, (Option<SystemTime>, u64)>;
+
+/// Each part's answer, or its error, by part and dataset.
+type Outcomes = BTreeMap<(u8, String), String>;
+
+/// One part's result, as printed by `run --format json`.
+#[derive(Deserialize)]
+struct RunRecord {
+    part: u8,
+    dataset: String,
+    answer: Option<Value>,
+    error: Option<String>,
+}
+
+/// Runs the day once, then again whenever its code or input changes, printing how the answers
+/// changed since the last run. Stops only when interrupted.
+pub fn run_watch(params: &AppParams, watch: &WatchParams) -> Result<()> {
+    if let Some(InputSource::Stdin) = params.input {
+        return Err(Box::new(Error::new(
+            "run --watch cannot read its input from stdin!",
+        )));
+    }
+    if !Path::new("Cargo.toml").is_file() {
+        return Err(Box::new(Error::new(
+            "run --watch rebuilds the solutions with cargo, so run it from the root of the repository!",
+        )));
+    }
+    let key = ChallengeKey::new(params.year, params.challenge_day.unwrap_or_default() as u8);
+    let paths = watched_paths(params);
+    let args = run_args(params, watch, key);
+    println!(
+        "Watching {} for changes to {}. Press Ctrl-C to stop.",
+        paths
+            .iter()
+            .map(|path| path.display().to_string())
+            .collect::<Vec<String>>()
+            .join(", "),
+        key
+    );
+
+    let mut previous: Option<Outcomes> = None;
+    let mut seen = snapshot(&paths);
+    loop {
+        match run_once(&args) {
+            Ok(outcomes) => {
+                for line in diff_outcomes(previous.as_ref(), &outcomes) {
+                    println!("{}", line);
+                }
+                previous = Some(outcomes);
+            }
+            Err(err) => eprintln!("{}", describe_error(err.as_ref())),
+        }
+        let changed = loop {
+            thread::sleep(POLL_INTERVAL);
+            let current = snapshot(&paths);
+            let changed = changed_paths(&seen, &current);
+            if !changed.is_empty() {
+                break changed;
+            }
+        };
+        thread::sleep(SETTLE_TIME);
+        seen = snapshot(&paths);
+        println!(
+            "\n{} changed; running {} again.",
+            match changed.as_slice() {
+                [path] => path.display().to_string(),
+                [path, rest @ ..] => format!("{} and {} more", path.display(), rest.len()),
+                [] => unreachable!("Only runs after a change"),
+            },
+            key
+        );
+    }
+}
+
+/// The solution sources, the project's config file and wherever the day's input is read from.
+fn watched_paths(params: &AppParams) -> Vec<PathBuf> {
+    let mut paths: Vec<PathBuf> = WATCHED_SOURCES
+        .iter()
+        .chain(&CONFIG_FILE_NAMES)
+        .map(PathBuf::from)
+        .collect();
+    match &params.input {
+        Some(InputSource::File(path)) => paths.push(path.clone()),
+        _ => {
+            paths.push(challenges_file_path(params));
+            paths.push(PathBuf::from(INPUTS_DIR));
+        }
+    }
+    paths
+}
+
+/// The arguments for running the day once, with answers printed as JSON.
+fn run_args(params: &AppParams, watch: &WatchParams, key: ChallengeKey) -> Vec<String> {
+    let mut args: Vec<String> = vec![
+        String::from("run"),
+        String::from("--year"),
+        key.year.to_string(),
+        String::from("--day"),
+        key.day.to_string(),
+        String::from("--format"),
+        String::from("json"),
+    ];
+    if let Some(part) = params.challenge_part {
+        args.extend([String::from("--part"), part.to_string()]);
+    }
+    if let Some(path) = &params.challenges_path {
+        args.extend([String::from("--challenges"), path.display().to_string()]);
+    }
+    if let Some(InputSource::File(path)) = &params.input {
+        args.extend([String::from("--input"), path.display().to_string()]);
+    }
+    match &params.example {
+        Some(name) => args.extend([String::from("--example"), name.clone()]),
+        None if params.use_example_data => args.push(String::from("--use-example-data")),
+        // Said explicitly, so a config file that picks an example doesn't win.
+        None if watch.use_puzzle_input => args.push(String::from("--use-puzzle-input")),
+        // An input file replaces the puzzle input, so it is used as given.
+        None if params.input.is_some() => {}
+        None => args.push(String::from("--use-example-data")),
+    }
+    args
+}
+
+/// Builds and runs the solutions with cargo, so code changes are picked up.
+fn run_once(args: &[String]) -> Result<Outcomes> {
+    let mut cargo = Command::new("cargo");
+    cargo.args(["run", "--quiet"]);
+    if !cfg!(debug_assertions) {
+        cargo.arg("--release");
+    }
+    let output = cargo
+        .arg("--")
+        .args(args)
+        .output()
+        .map_err(|err| Error::io("Could not run cargo!", err))?;
+    match parse_outcomes(&String::from_utf8_lossy(&output.stdout)) {
+        Ok(outcomes) if !outcomes.is_empty() => Ok(outcomes),
+        _ => Err(Box::new(Error::new(&format!(
+            "The solution could not be built or run:\n{}",
+            String::from_utf8_lossy(&output.stderr).trim_end()
+        )))),
+    }
+}
+
+fn parse_outcomes(json: &str) -> Result<Outcomes> {
+    let records: Vec<RunRecord> = serde_json::from_str(json)?;
+    Ok(records
+        .into_iter()
+        .map(|record| {
+            let outcome = match (record.answer, record.error) {
+                (_, Some(error)) => format!("error: {}", error),
+                (Some(Value::String(answer)), None) => answer,
+                (Some(answer), None) => answer.to_string(),
+                (None, None) => String::new(),
+            };
+            ((record.part, record.dataset), outcome)
+        })
+        .collect())
+}
+
+/// One line per part and dataset, saying whether the answer changed since `previous`. Multi-line
+/// answers that changed are shown in full, old above new.
+fn diff_outcomes(previous: Option<&Outcomes>, current: &Outcomes) -> Vec<String> {
+    let mut lines = vec![];
+    for ((part, dataset), outcome) in current {
+        let label = format!("Part {} ({})", part, dataset);
+        match previous.and_then(|previous| previous.get(&(*part, dataset.clone()))) {
+            None => lines.push(format!("{}: {}", label, outcome)),
+            Some(old) if old == outcome => {
+                lines.push(format!("{}: {} (unchanged)", label, outcome))
+            }
+            Some(old) if !old.contains('\n') && !outcome.contains('\n') => {
+                lines.push(format!("{}: {} -> {}", label, old, outcome))
+            }
+            Some(old) => {
+                lines.push(format!("{} changed:", label));
+                lines.extend(old.lines().map(|line| format!("- {}", line)));
+                lines.extend(outcome.lines().map(|line| format!("+ {}", line)));
+            }
+        }
+    }
+    lines
+}
+
+/// Records every file under `paths`. Paths that don't exist yet are skipped, so creating one
+/// counts as a change.
+fn snapshot(paths: &[PathBuf]) -> Snapshot {
+    let mut snapshot = Snapshot::new();
+    let mut pending: Vec<PathBuf> = paths.to_vec();
+    while let Some(path) = pending.pop() {
+        let metadata = match fs::metadata(&path) {
+            Ok(metadata) => metadata,
+            Err(_) => continue,
+        };
+        if metadata.is_dir() {
+            if let Ok(entries) = fs::read_dir(&path) {
+                pending.extend(entries.flatten().map(|entry| entry.path()));
+            }
+        } else {
+            snapshot.insert(path, (metadata.modified().ok(), metadata.len()));
+        }
+    }
+    snapshot
+}
+
+/// The files that were added, removed or modified between two snapshots.
+fn changed_paths(before: &Snapshot, after: &Snapshot) -> Vec<PathBuf> {
+    let mut changed: Vec<PathBuf> = after
+        .iter()
+        .filter(|(path, stamp)| before.get(*path) != Some(stamp))
+        .map(|(path, _)| path.clone())
+        .collect();
+    changed.extend(
+        before
+            .keys()
+            .filter(|path| !after.contains_key(*path))
+            .cloned(),
+    );
+    changed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aoc::test_dir::TestDir;
+    use crate::cli::parse_args;
+    use crate::common::Command;
+    use crate::config::Config;
+
+    #[test]
+    fn changes_are_found_by_polling_metadata() -> Result<()> {
+        let dir = TestDir::new("watched");
+        fs::create_dir_all(dir.join("sub"))?;
+        fs::write(dir.join("sub").join("day_1.rs"), "fn main() {}")?;
+        let missing = dir.join("input.txt");
+        let paths = vec![dir.to_path_buf(), missing.clone()];
+
+        let before = snapshot(&paths);
+        assert_eq!(1, before.len());
+        assert!(changed_paths(&before, &snapshot(&paths)).is_empty());
+        fs::write(dir.join("sub").join("day_1.rs"), "fn main() { }")?;
+        fs::write(&missing, "1\n2")?;
+        let after = snapshot(&paths);
+        assert_eq!(
+            vec![missing.clone(), dir.join("sub").join("day_1.rs")],
+            changed_paths(&before, &after)
+        );
+        fs::remove_file(&missing)?;
+        assert_eq!(vec![missing], changed_paths(&after, &snapshot(&paths)));
+        Ok(())
+    }
+
+    #[test]
+    fn answers_are_compared_with_the_last_run() -> Result<()> {
+        let first = parse_outcomes(
+            r##"[{"year": 2021, "day": 1, "part": 1, "dataset": "example", "answer": 7, "wall_time_ms": 0.1, "error": null},
+                {"year": 2021, "day": 1, "part": 2, "dataset": "example", "answer": "#.\n.#", "wall_time_ms": 0.1, "error": null}]"##,
+        )?;
+        assert_eq!(
+            vec!["Part 1 (example): 7", "Part 2 (example): #.\n.#"],
+            diff_outcomes(None, &first)
+        );
+        let second = parse_outcomes(
+            r##"[{"year": 2021, "day": 1, "part": 1, "dataset": "example", "answer": null, "wall_time_ms": 0.1, "error": "Invalid number x!"},
+                {"year": 2021, "day": 1, "part": 2, "dataset": "example", "answer": "#.\n##", "wall_time_ms": 0.1, "error": null}]"##,
+        )?;
+        assert_eq!(
+            vec![
+                "Part 1 (example): 7 -> error: Invalid number x!",
+                "Part 2 (example) changed:",
+                "- #.",
+                "- .#",
+                "+ #.",
+                "+ ##"
+            ],
+            diff_outcomes(Some(&first), &second)
+        );
+        assert_eq!(
+            "Part 1 (example): error: Invalid number x! (unchanged)",
+            diff_outcomes(Some(&second), &second)[0]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn runs_use_the_first_example_unless_told_otherwise() -> Result<()> {
+        let args = |line: &str| -> Result<Vec<String>> {
+            let params = parse_args(
+                line.split_whitespace().map(String::from),
+                &Config::default(),
+            )?;
+            match &params.command {
+                Command::Watch(watch) => Ok(run_args(&params, watch, ChallengeKey::new(2021, 5))),
+                _ => Err(Box::new(Error::new("Not a watch command!"))),
+            }
+        };
+        assert_eq!(
+            "run --year 2021 --day 5 --format json --part 2 --use-example-data",
+            args("aoc2021 run --watch --day 5 --part 2")?.join(" ")
+        );
+        assert!(args("aoc2021 run --watch --day 5 --use-puzzle-input")?
+            .ends_with(&[String::from("--use-puzzle-input")]));
+        assert!(args("aoc2021 run --watch --day 5 --input day_5.txt")?
+            .ends_with(&[String::from("--input"), String::from("day_5.txt")]));
+        assert!(args("aoc2021 run --watch --day 5 --example larger")?
+            .ends_with(&[String::from("--example"), String::from("larger")]));
+        Ok(())
+    }
+}
@@ -0,0 +1,532 @@
+use std::env;
+use std::iter::Peekable;
+use std::ops::RangeInclusive;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use crate::common::{
+    AppParams, Command, FetchParams, ImportParams, InputSource, NewParams, OutputFormat,
+    ServerParams, SubmitParams, WatchParams,
+};
+use crate::config::{Config, DefaultDataset};
+use crate::{Error, Result};
+
+pub const ALL_CHALLENGE_DAYS: RangeInclusive<u32> = 1..=25;
+pub const DEFAULT_YEAR: u16 = 2021;
+pub const FIRST_YEAR: u16 = 2015;
+const CHALLENGE_PARTS: RangeInclusive<u32> = 1..=2;
+const DEFAULT_BENCH_ITERATIONS: u32 = 10;
+const DEFAULT_JOBS: usize = 1;
+
+pub const USAGE: &str = "\
+Usage: aoc2021 [COMMAND] [OPTIONS]
+
+Commands:
+  (none)     Pick a challenge and what to do with it interactively
+  calendar   Browse, run and check the days on a full-screen calendar
+  list       List the stored challenges and which parts have solutions
+  show       Print a challenge's examples and puzzle input
+  run        Run solutions and print a summary of their answers
+  bench      Time the parse and solve phases of solutions
+  verify     Check solutions against the recorded answers
+  import     Add or update a challenge in challenges.json
+  fetch      Download a day's puzzle input
+  submit     Submit an answer for a day and part
+  new        Create a solution for a day from the template, and a placeholder challenge
+  help       Print this message
+
+Options:
+  --year YYYY              Select the year of the event (default 2021)
+  --day N | A..B | A..=B   Select a day, or an inclusive range of days (1-25)
+  --all                    Select every day
+  --part N                 Select a part (1 or 2)
+  --use-example-data       Use the first example instead of the puzzle input
+  --use-puzzle-input       Use the puzzle input, even if the config file picks an example
+  --example NAME           Use the named example instead of the puzzle input
+  --input PATH | -         Read the puzzle input from a file, or from stdin
+  --challenges PATH        Read challenges from a JSON file or an inputs directory
+  --format text|json|tsv   Output format for run and bench
+  --trace                  Print intermediate simulation states to stderr
+  --watch                  run: rebuild and run a --day again whenever its code or input changes
+  --jobs N                 Worker threads for run and verify (default 1)
+  --color on|off           Color the calendar (default on, unless $NO_COLOR is set)
+  --iterations N           Iterations per solution for bench (default 10)
+  --data PATH              import: the puzzle input file
+  --example [NAME=]PATH    import: an example file; may be repeated
+  --parts N                import: the number of parts
+  --force                  import: overwrite an existing challenge
+  --session TOKEN          fetch, submit: session cookie (default $AOC_SESSION)
+  --base-url URL           fetch, submit: server URL (default $AOC2021_BASE_URL)
+  --answer ANSWER          submit: send this instead of running the solution
+  --module NAME            new: also create the sub::NAME module, with a test skeleton
+  -h, --help               Print this message
+
+Defaults for --challenges, the dataset, --format, --color and --jobs, and settings for the
+solutions, can be put in aoc.toml or aoc.json, in the current directory or in ~/.config/aoc2021.
+For example:
+
+  challenges = \"data/challenges.json\"
+  dataset = \"example\"          # puzzle, example, or the name of an example
+  format = \"json\"
+  color = false
+  jobs = 4
+
+  [params.2021.6]
+  part_1_days = 18
+
+Exits with status 0 on success, 1 if the command fails and 2 if the arguments are invalid.";
+
+/// Parses the command line, taking defaults for the options it leaves out from `config`.
+pub fn parse_args<I: Iterator<Item = String>>(args: I, config: &Config) -> Result<AppParams> {
+    let mut args = args.peekable();
+    let program_name = args.next().unwrap_or_default();
+
+    let command_name = match args.peek() {
+        Some(arg) if !arg.starts_with('-') => args.next(),
+        _ => None,
+    };
+    let importing = command_name.as_deref() == Some("import");
+
+    let mut help = command_name.as_deref() == Some("help");
+    let mut use_example_data = false;
+    let mut trace = false;
+    let mut example: Option<String> = None;
+    let mut year = DEFAULT_YEAR;
+    let mut challenge_day: Option<u32> = None;
+    let mut challenge_part: Option<u32> = None;
+    let mut batch_days: Option<RangeInclusive<u32>> = None;
+    let mut iterations = DEFAULT_BENCH_ITERATIONS;
+    let mut jobs: Option<usize> = None;
+    let mut output_format: Option<OutputFormat> = None;
+    let mut color: Option<bool> = None;
+    let mut input: Option<InputSource> = None;
+    let mut challenges_path: Option<PathBuf> = None;
+    let mut import_examples: Vec<(String, PathBuf)> = vec![];
+    let mut import_data: Option<PathBuf> = None;
+    let mut import_parts: Option<u8> = None;
+    let mut force = false;
+    let mut session: Option<String> = None;
+    let mut base_url: Option<String> = None;
+    let mut answer: Option<String> = None;
+    let mut module: Option<String> = None;
+    let mut watch = false;
+    let mut use_puzzle_input = false;
+    let mut dataset_given = false;
+
+    while let Some(next_arg) = args.peek() {
+        match next_arg.as_str() {
+            "-h" | "--help" => help = consume_boolean_option(&mut args),
+            "--all" => {
+                consume_boolean_option(&mut args);
+                batch_days = Some(ALL_CHALLENGE_DAYS);
+            }
+            "--year" => {
+                let year_num = consume_u32_option(&mut args)?;
+                year = match u16::try_from(year_num) {
+                    Ok(year) if year >= FIRST_YEAR => year,
+                    _ => {
+                        return Err(Box::new(Error::new(&format!(
+                            "Year {} is out of range! The first event was in {}.",
+                            year_num, FIRST_YEAR
+                        ))))
+                    }
+                }
+            }
+            "--day" => {
+                let day_str = consume_string_option(&mut args)?;
+                match day_str.split_once("..") {
+                    Some((start, end)) => {
+                        let end = end.strip_prefix('=').unwrap_or(end);
+                        batch_days = Some(parse_day_range(start, end)?)
+                    }
+                    None => challenge_day = Some(parse_day(&day_str)?),
+                }
+            }
+            "--part" => {
+                let part = consume_u32_option(&mut args)?;
+                if !CHALLENGE_PARTS.contains(&part) {
+                    return Err(Box::new(Error::new(&format!(
+                        "Part {} is out of range! Parts are 1 or 2.",
+                        part
+                    ))));
+                }
+                challenge_part = Some(part)
+            }
+            "--example" if importing => {
+                let example_str = consume_string_option(&mut args)?;
+                import_examples.push(match example_str.split_once('=') {
+                    Some((name, path)) => (String::from(name), PathBuf::from(path)),
+                    None => (String::from("example"), PathBuf::from(example_str)),
+                })
+            }
+            "--example" => {
+                example = Some(consume_string_option(&mut args)?);
+                dataset_given = true;
+            }
+            "--use-example-data" => {
+                use_example_data = consume_boolean_option(&mut args);
+                dataset_given = true;
+            }
+            "--trace" => trace = consume_boolean_option(&mut args),
+            "--watch" => watch = consume_boolean_option(&mut args),
+            "--use-puzzle-input" => {
+                use_puzzle_input = consume_boolean_option(&mut args);
+                dataset_given = true;
+            }
+            "--input" => {
+                input = Some(InputSource::from_str(&consume_string_option(&mut args)?)?);
+                dataset_given = true;
+            }
+            "--challenges" => {
+                challenges_path = Some(PathBuf::from(consume_string_option(&mut args)?))
+            }
+            "--format" => {
+                output_format = Some(OutputFormat::from_str(&consume_string_option(&mut args)?)?)
+            }
+            "--color" => color = Some(parse_on_off("--color", &consume_string_option(&mut args)?)?),
+            "--iterations" => match consume_u32_option(&mut args)? {
+                0 => return Err(Box::new(Error::new("--iterations must be at least 1!"))),
+                n => iterations = n,
+            },
+            "--jobs" => match consume_u32_option(&mut args)? {
+                0 => return Err(Box::new(Error::new("--jobs must be at least 1!"))),
+                n => jobs = Some(n as usize),
+            },
+            "--data" => import_data = Some(PathBuf::from(consume_string_option(&mut args)?)),
+            "--parts" => {
+                let parts = consume_u32_option(&mut args)?;
+                if !CHALLENGE_PARTS.contains(&parts) {
+                    return Err(Box::new(Error::new(&format!(
+                        "A challenge has 1 or 2 parts, not {}!",
+                        parts
+                    ))));
+                }
+                import_parts = Some(u8::try_from(parts)?)
+            }
+            "--force" => force = consume_boolean_option(&mut args),
+            "--session" => session = Some(consume_string_option(&mut args)?),
+            "--base-url" => base_url = Some(consume_string_option(&mut args)?),
+            "--answer" => answer = Some(consume_string_option(&mut args)?),
+            "--module" => module = Some(parse_module_name(&consume_string_option(&mut args)?)?),
+            s => return Err(Box::new(Error::new(&format!("Unrecognized option {}!", s)))),
+        };
+    }
+
+    if use_puzzle_input && (use_example_data || example.is_some()) {
+        return Err(Box::new(Error::new(
+            "--use-puzzle-input cannot be used with --use-example-data or --example!",
+        )));
+    }
+    if watch && !help && command_name.as_deref() != Some("run") {
+        return Err(Box::new(Error::new("--watch only works with run!")));
+    }
+
+    if !dataset_given {
+        match &config.dataset {
+            Some(DefaultDataset::Puzzle) => use_puzzle_input = true,
+            Some(DefaultDataset::FirstExample) => use_example_data = true,
+            Some(DefaultDataset::Example(name)) => example = Some(name.clone()),
+            None => {}
+        }
+    }
+
+    let command = if help {
+        Command::Help
+    } else {
+        match command_name.as_deref() {
+            None => Command::Interactive,
+            Some("calendar") => Command::Calendar,
+            Some("list") => Command::List,
+            Some("show") => Command::Show(require_day("show", challenge_day)?),
+            Some("run") if watch => {
+                if challenge_day.is_none() || batch_days.is_some() {
+                    return Err(Box::new(Error::new("run --watch requires a single --day!")));
+                }
+                Command::Watch(WatchParams { use_puzzle_input })
+            }
+            Some("run") => Command::Run,
+            Some("bench") => Command::Bench(iterations),
+            Some("verify") => Command::Verify,
+            Some("import") => Command::Import(ImportParams {
+                day: u8::try_from(require_day("import", challenge_day)?)?,
+                parts: import_parts,
+                examples: import_examples,
+                data: import_data,
+                force,
+            }),
+            Some("fetch") => Command::Fetch(FetchParams {
+                day: require_day("fetch", challenge_day)?,
+                server: ServerParams { session, base_url },
+            }),
+            Some("submit") => match challenge_part {
+                Some(part) => Command::Submit(SubmitParams {
+                    day: require_day("submit", challenge_day)?,
+                    part,
+                    answer,
+                    server: ServerParams { session, base_url },
+                }),
+                None => return Err(Box::new(Error::new("submit requires --part!"))),
+            },
+            Some("new") => Command::New(NewParams {
+                day: require_day("new", challenge_day)?,
+                module,
+            }),
+            Some(s) => {
+                return Err(Box::new(Error::new(&format!(
+                    "Unrecognized command {}!",
+                    s
+                ))))
+            }
+        }
+    };
+
+    Ok(AppParams {
+        program_name,
+        command,
+        use_example_data,
+        example,
+        trace,
+        year,
+        challenge_day,
+        challenge_part,
+        batch_days,
+        jobs: jobs.or(config.jobs).unwrap_or(DEFAULT_JOBS),
+        output_format: output_format
+            .or(config.output_format)
+            .unwrap_or(OutputFormat::Text),
+        color: color
+            .or(config.color)
+            .unwrap_or_else(|| env::var_os("NO_COLOR").is_none()),
+        input,
+        challenges_path: challenges_path.or_else(|| config.challenges_path.clone()),
+        day_params: config.day_params.clone(),
+    })
+}
+
+fn parse_day(day_str: &str) -> Result<u32> {
+    let day = u32::from_str(day_str)?;
+    if ALL_CHALLENGE_DAYS.contains(&day) {
+        Ok(day)
+    } else {
+        Err(Box::new(Error::new(&format!(
+            "Day {} is out of range! Days are 1 to 25.",
+            day
+        ))))
+    }
+}
+
+fn parse_day_range(start: &str, end: &str) -> Result<RangeInclusive<u32>> {
+    let (start, end) = (parse_day(start)?, parse_day(end)?);
+    if start > end {
+        return Err(Box::new(Error::new(&format!(
+            "Day range {}..={} is empty!",
+            start, end
+        ))));
+    }
+    Ok(start..=end)
+}
+
+/// Module names become file names and `mod` declarations, so they must be snake_case.
+fn parse_module_name(name: &str) -> Result<String> {
+    let valid = name.starts_with(|c: char| c.is_ascii_lowercase())
+        && name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_');
+    if valid {
+        Ok(String::from(name))
+    } else {
+        Err(Box::new(Error::new(&format!(
+            "Module name {} is not a snake_case identifier!",
+            name
+        ))))
+    }
+}
+
+fn parse_on_off(option: &str, value: &str) -> Result<bool> {
+    match value {
+        "on" => Ok(true),
+        "off" => Ok(false),
+        _ => Err(Box::new(Error::new(&format!(
+            "{} must be on or off, not {}!",
+            option, value
+        )))),
+    }
+}
+
+fn require_day(command: &str, day: Option<u32>) -> Result<u32> {
+    match day {
+        Some(day) => Ok(day),
+        None => Err(Box::new(Error::new(&format!(
+            "{} requires a single --day!",
+            command
+        )))),
+    }
+}
+
+fn consume_boolean_option<I: Iterator<Item = String>>(args: &mut Peekable<I>) -> bool {
+    args.next();
+    true
+}
+
+fn consume_string_option<I: Iterator<Item = String>>(args: &mut Peekable<I>) -> Result<String> {
+    let option = args.next().unwrap_or_default();
+    match args.next() {
+        Some(s) => Ok(s),
+        None => Err(Box::new(Error::new(&format!(
+            "Missing value for {}!",
+            option
+        )))),
+    }
+}
+
+fn consume_u32_option<I: Iterator<Item = String>>(args: &mut Peekable<I>) -> Result<u32> {
+    let option = args.peek().cloned().unwrap_or_default();
+    let str_val = consume_string_option(args)?;
+    match u32::from_str(&str_val) {
+        Ok(u32_val) => Ok(u32_val),
+        Err(err) => Err(Box::new(Error::new(&format!("{}: {}", option, err)))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &str) -> Result<AppParams> {
+        parse_args(
+            format!("aoc2021 {}", args)
+                .split_whitespace()
+                .map(String::from),
+            &Config::default(),
+        )
+    }
+
+    #[test]
+    fn subcommands_are_parsed() -> Result<()> {
+        assert!(matches!(parse("")?.command, Command::Interactive));
+        assert!(matches!(parse("list")?.command, Command::List));
+        assert!(matches!(parse("calendar")?.command, Command::Calendar));
+        assert!(matches!(
+            parse("run --watch --day 3 --use-puzzle-input")?.command,
+            Command::Watch(WatchParams {
+                use_puzzle_input: true
+            })
+        ));
+        assert!(matches!(
+            parse("new --day 13 --module sonar_2")?.command,
+            Command::New(NewParams { day: 13, module: Some(ref module) }) if module == "sonar_2"
+        ));
+        assert!(matches!(parse("show --day 3")?.command, Command::Show(3)));
+        assert!(matches!(parse("bench --all")?.command, Command::Bench(10)));
+        assert!(matches!(
+            parse("bench --iterations 3")?.command,
+            Command::Bench(3)
+        ));
+        let params = parse("run --day 1..=5 --part 2")?;
+        assert!(matches!(params.command, Command::Run));
+        assert_eq!(Some(1..=5), params.batch_days);
+        assert_eq!(Some(2), params.challenge_part);
+        assert_eq!(1, params.jobs);
+        assert_eq!(DEFAULT_YEAR, params.year);
+        assert_eq!(2022, parse("run --year 2022 --all")?.year);
+        assert_eq!(4, parse("verify --all --jobs 4")?.jobs);
+        Ok(())
+    }
+
+    #[test]
+    fn help_wins_over_other_arguments() -> Result<()> {
+        assert!(matches!(parse("run --help")?.command, Command::Help));
+        assert!(matches!(parse("-h")?.command, Command::Help));
+        assert!(matches!(parse("help")?.command, Command::Help));
+        Ok(())
+    }
+
+    #[test]
+    fn days_and_parts_are_validated() -> Result<()> {
+        assert!(parse("run --day 0").is_err());
+        assert!(parse("run --day 26").is_err());
+        assert!(parse("run --day 5..3").is_err());
+        assert!(parse("run --day 1..=30").is_err());
+        assert!(parse("run --part 3").is_err());
+        assert!(parse("run --year 2014").is_err());
+        assert!(parse("run --year 70000").is_err());
+        assert!(parse("show").is_err());
+        assert!(parse("new").is_err());
+        assert!(parse("run --watch --all").is_err());
+        assert!(parse("new --day 1 --module Sonar").is_err());
+        assert!(parse("new --day 1 --module ../sonar").is_err());
+        assert!(parse("import --day 1 --parts 0").is_err());
+        assert!(parse("import --day 1 --parts 258").is_err());
+        assert!(parse("import --day 26 --parts 1").is_err());
+        assert!(matches!(
+            parse("import --day 25 --parts 1")?.command,
+            Command::Import(ImportParams {
+                day: 25,
+                parts: Some(1),
+                ..
+            })
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn invalid_arguments_are_rejected() {
+        assert!(parse("frobnicate").is_err());
+        assert!(parse("run --frobnicate").is_err());
+        assert!(parse("run --day").is_err());
+        assert!(parse("run --jobs 0").is_err());
+        assert!(parse("bench --day 1 --iterations 0").is_err());
+        assert!(parse("calendar --color sometimes").is_err());
+        assert!(parse("verify --watch --day 1").is_err());
+        assert!(parse("run --day 1 --use-example-data --use-puzzle-input").is_err());
+        assert!(parse("run --day 1 --use-puzzle-input --example larger").is_err());
+        assert_eq!(
+            "--jobs: invalid digit found in string",
+            parse("run --jobs four").err().unwrap().to_string()
+        );
+    }
+
+    #[test]
+    fn config_fills_in_options_that_are_not_given() -> Result<()> {
+        let config = Config {
+            challenges_path: Some(PathBuf::from("private/challenges.json")),
+            dataset: Some(DefaultDataset::Example(String::from("larger"))),
+            output_format: Some(OutputFormat::Tsv),
+            color: Some(false),
+            jobs: Some(4),
+            ..Config::default()
+        };
+        let parse_with_config = |args: &str| {
+            parse_args(
+                format!("aoc2021 {}", args)
+                    .split_whitespace()
+                    .map(String::from),
+                &config,
+            )
+        };
+        let params = parse_with_config("run --day 1")?;
+        assert_eq!(Some(String::from("larger")), params.example);
+        assert_eq!(OutputFormat::Tsv, params.output_format);
+        assert!(!params.color);
+        assert_eq!(4, params.jobs);
+        assert_eq!(
+            Some(PathBuf::from("private/challenges.json")),
+            params.challenges_path
+        );
+
+        let params = parse_with_config("run --day 1 --format json --jobs 2 --color on")?;
+        assert_eq!(
+            (OutputFormat::Json, 2, true),
+            (params.output_format, params.jobs, params.color)
+        );
+        for args in [
+            "--use-puzzle-input",
+            "--use-example-data",
+            "--input day_1.txt",
+        ] {
+            let params = parse_with_config(&format!("run --day 1 {}", args))?;
+            assert_eq!(None, params.example, "{}", args);
+        }
+        Ok(())
+    }
+}
@@ -1,50 +1,134 @@
-mod helpers;
+pub mod bit_string;
+pub mod day_params;
+mod error;
+pub mod jobs;
 pub mod map2d;
+pub mod parsing;
+pub mod trace;
 pub mod vector;
 
 use core::result::Result as CoreResult;
-use std::error::Error as ErrorTrait;
-use std::fmt::{Debug, Display, Formatter};
+use std::ops::RangeInclusive;
+use std::path::PathBuf;
+use std::str::FromStr;
 
-pub use helpers::bit_lines;
-pub use helpers::first_line;
-pub use helpers::int_lines;
-pub use helpers::split_lines_on;
+pub use day_params::{AllDayParams, DayParams};
+pub use error::{describe_error, Error, Result};
 
 pub use map2d::Map2D;
 
+pub use parsing::{InputLine, Parse};
+
+pub use trace::Tracer;
+
 pub use vector::Component;
 pub use vector::Vector2;
 
 pub struct AppParams {
     pub program_name: String,
+    pub command: Command,
     pub use_example_data: bool,
+    pub example: Option<String>,
+    pub trace: bool,
+    pub year: u16,
     pub challenge_day: Option<u32>,
     pub challenge_part: Option<u32>,
+    pub batch_days: Option<RangeInclusive<u32>>,
+    pub jobs: usize,
+    pub output_format: OutputFormat,
+    pub color: bool,
+    pub input: Option<InputSource>,
+    pub challenges_path: Option<PathBuf>,
+    /// Settings for the solutions, from the config file.
+    pub day_params: AllDayParams,
+}
+
+pub enum Command {
+    Interactive,
+    Calendar,
+    Help,
+    List,
+    Show(u32),
+    Run,
+    Watch(WatchParams),
+    Bench(u32),
+    Verify,
+    Import(ImportParams),
+    Fetch(FetchParams),
+    Submit(SubmitParams),
+    New(NewParams),
+}
+
+pub struct ImportParams {
+    pub day: u8,
+    pub parts: Option<u8>,
+    pub examples: Vec<(String, PathBuf)>,
+    pub data: Option<PathBuf>,
+    pub force: bool,
 }
 
-pub type Result<T> = CoreResult<T, Box<dyn ErrorTrait>>;
+pub struct ServerParams {
+    pub session: Option<String>,
+    pub base_url: Option<String>,
+}
+
+pub struct FetchParams {
+    pub day: u32,
+    pub server: ServerParams,
+}
+
+pub struct WatchParams {
+    /// Run on the puzzle input rather than the first example.
+    pub use_puzzle_input: bool,
+}
 
-#[derive(Debug)]
-pub struct Error {
-    message: String,
+pub struct NewParams {
+    pub day: u32,
+    /// The `sub` module to create beside the solution, if any.
+    pub module: Option<String>,
 }
 
-impl Error {
-    pub fn new<T: ?Sized>(message: &T) -> Error
-    where
-        T: ToString,
-    {
-        Error {
-            message: message.to_string(),
+pub struct SubmitParams {
+    pub day: u32,
+    pub part: u32,
+    pub answer: Option<String>,
+    pub server: ServerParams,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum InputSource {
+    Stdin,
+    File(PathBuf),
+}
+
+impl FromStr for InputSource {
+    type Err = Error;
+
+    fn from_str(s: &str) -> CoreResult<Self, Self::Err> {
+        match s {
+            "" => Err(Error::new("Missing path for --input!")),
+            "-" => Ok(InputSource::Stdin),
+            path => Ok(InputSource::File(PathBuf::from(path))),
         }
     }
 }
 
-impl Display for Error {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.message)
-    }
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum OutputFormat {
+    Text,
+    Json,
+    Tsv,
 }
 
-impl ErrorTrait for Error {}
+impl FromStr for OutputFormat {
+    type Err = Error;
+
+    fn from_str(s: &str) -> CoreResult<Self, Self::Err> {
+        match s {
+            "text" => Ok(OutputFormat::Text),
+            "json" => Ok(OutputFormat::Json),
+            "tsv" => Ok(OutputFormat::Tsv),
+            _ => Err(Error::new(&format!("Unknown output format {}!", s))),
+        }
+    }
+}
@@ -0,0 +1,183 @@
+use std::fmt::{Display, Formatter};
+
+use super::parsing::InputLine;
+use super::Result;
+
+type Word = u64;
+const WORD_BITS: usize = Word::BITS as usize;
+
+/// A fixed-width string of bits, packed into words. Bit 0 is the leftmost, most significant bit
+/// as written.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct BitString {
+    words: Vec<Word>,
+    len: usize,
+}
+
+impl BitString {
+    pub fn new(len: usize) -> BitString {
+        BitString {
+            words: vec![0; len.div_ceil(WORD_BITS)],
+            len,
+        }
+    }
+
+    pub fn from_bits<T: IntoIterator<Item = bool>>(bits: T) -> BitString {
+        let bits: Vec<bool> = bits.into_iter().collect();
+        let mut bit_string = BitString::new(bits.len());
+        for (i, bit) in bits.into_iter().enumerate() {
+            bit_string.set(i, bit);
+        }
+        bit_string
+    }
+
+    /// Parses a string of `0`s and `1`s, pointing at the first other character.
+    pub fn parse_line(line: &InputLine, text: &str) -> Result<BitString> {
+        let text = text.trim();
+        let mut bit_string = BitString::new(text.chars().count());
+        for (i, (offset, c)) in text.char_indices().enumerate() {
+            match c {
+                '0' => {}
+                '1' => bit_string.set(i, true),
+                _ => {
+                    return Err(Box::new(line.error(
+                        &text[offset..offset + c.len_utf8()],
+                        &format!("Expected a binary digit, found {}", c),
+                    )))
+                }
+            }
+        }
+        Ok(bit_string)
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn get(&self, i: usize) -> bool {
+        assert!(
+            i < self.len,
+            "Bit {} is out of range for {} bits!",
+            i,
+            self.len
+        );
+        self.words[i / WORD_BITS] & (1 << (i % WORD_BITS)) != 0
+    }
+
+    pub fn set(&mut self, i: usize, bit: bool) {
+        assert!(
+            i < self.len,
+            "Bit {} is out of range for {} bits!",
+            i,
+            self.len
+        );
+        let mask = 1 << (i % WORD_BITS);
+        if bit {
+            self.words[i / WORD_BITS] |= mask;
+        } else {
+            self.words[i / WORD_BITS] &= !mask;
+        }
+    }
+
+    /// The bits read as a binary number, or `None` if there are more than 64 of them.
+    pub fn to_u64(&self) -> Option<u64> {
+        if self.len > 64 {
+            return None;
+        }
+        Some((0..self.len).fold(0, |acc, i| (acc << 1) | self.get(i) as u64))
+    }
+
+    /// The positions of the set bits, in order.
+    fn ones(&self) -> impl Iterator<Item = usize> + '_ {
+        self.words
+            .iter()
+            .enumerate()
+            .flat_map(|(word_index, &word)| {
+                let mut remainder = word;
+                std::iter::from_fn(move || {
+                    if remainder == 0 {
+                        return None;
+                    }
+                    let bit = remainder.trailing_zeros() as usize;
+                    remainder &= remainder - 1;
+                    Some(word_index * WORD_BITS + bit)
+                })
+            })
+    }
+}
+
+impl Display for BitString {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for i in 0..self.len {
+            write!(f, "{}", if self.get(i) { '1' } else { '0' })?;
+        }
+        Ok(())
+    }
+}
+
+/// The number of set bits in each column of `bit_strings`, as wide as the widest of them.
+pub fn count_ones_per_column<'a, T>(bit_strings: T) -> Vec<usize>
+where
+    T: IntoIterator<Item = &'a BitString>,
+{
+    let mut counts: Vec<usize> = vec![];
+    for bit_string in bit_strings {
+        if counts.len() < bit_string.len() {
+            counts.resize(bit_string.len(), 0);
+        }
+        for i in bit_string.ones() {
+            counts[i] += 1;
+        }
+    }
+    counts
+}
+
+/// The number of `bit_strings` with `column` set.
+pub fn count_ones_in_column<'a, T>(bit_strings: T, column: usize) -> usize
+where
+    T: IntoIterator<Item = &'a BitString>,
+{
+    bit_strings
+        .into_iter()
+        .filter(|bit_string| column < bit_string.len() && bit_string.get(column))
+        .count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::parsing::{lines, parse_input};
+
+    #[test]
+    fn bits_survive_word_boundaries() {
+        let mut bits = BitString::new(130);
+        bits.set(0, true);
+        bits.set(64, true);
+        bits.set(129, true);
+        assert!(bits.get(64) && !bits.get(63) && !bits.get(65));
+        assert_eq!(None, bits.to_u64());
+        assert_eq!(vec![0, 64, 129], bits.ones().collect::<Vec<usize>>());
+    }
+
+    #[test]
+    fn columns_are_counted() -> Result<()> {
+        let numbers: Vec<BitString> =
+            parse_input("10110\n00111\n11100", lines(BitString::parse_line))?;
+        assert_eq!(Some(22), numbers[0].to_u64());
+        assert_eq!("00111", numbers[1].to_string());
+        assert_eq!(vec![2, 1, 3, 2, 1], count_ones_per_column(&numbers));
+        assert_eq!(3, count_ones_in_column(&numbers, 2));
+        Ok(())
+    }
+
+    #[test]
+    fn stray_characters_are_rejected() {
+        let err = parse_input("10110\n10210", lines(BitString::parse_line))
+            .err()
+            .unwrap();
+        assert_eq!(
+            "Expected a binary digit, found 2 at line 2, column 3\n2 | 10210\n  |   ^",
+            err.to_string()
+        );
+    }
+}
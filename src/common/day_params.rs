@@ -0,0 +1,22 @@
+use std::collections::BTreeMap;
+use std::sync::RwLock;
+
+/// A day's named settings, such as how many steps a simulation runs for.
+pub type DayParams = BTreeMap<String, u64>;
+
+/// The settings for each year and day that has any.
+pub type AllDayParams = BTreeMap<(u16, u8), DayParams>;
+
+static DAY_PARAMS: RwLock<AllDayParams> = RwLock::new(BTreeMap::new());
+
+/// Sets what solutions read with `day_param` for every day in `params`. Other days keep theirs.
+pub fn set_day_params(params: &AllDayParams) {
+    let mut day_params = DAY_PARAMS.write().unwrap_or_else(|err| err.into_inner());
+    day_params.extend(params.clone());
+}
+
+/// The value of the setting `name` for a year and day, if one was given.
+pub fn day_param(year: u16, day: u8, name: &str) -> Option<u64> {
+    let day_params = DAY_PARAMS.read().unwrap_or_else(|err| err.into_inner());
+    day_params.get(&(year, day))?.get(name).copied()
+}
@@ -0,0 +1,190 @@
+use core::result::Result as CoreResult;
+use std::error::Error as ErrorTrait;
+use std::fmt::{Display, Formatter};
+
+use super::parsing::Location;
+
+pub type Result<T> = CoreResult<T, Box<dyn ErrorTrait>>;
+
+/// Everything that can go wrong while loading and solving challenges. Callers holding a boxed
+/// error can `downcast_ref::<Error>()` to tell the kinds apart.
+#[derive(Debug)]
+pub enum Error {
+    /// Puzzle input that does not have the expected format, and where in the input it was.
+    Parse {
+        message: String,
+        location: Option<Location>,
+        source: Option<Box<dyn ErrorTrait>>,
+    },
+    /// No challenge is stored for the year and day.
+    ChallengeNotFound {
+        year: u16,
+        day: u8,
+        location: String,
+    },
+    /// No solution is implemented for the year, day and part.
+    SolutionNotFound { year: u16, day: u8, part: u8 },
+    /// Input that parses, but breaks the rules of the puzzle.
+    InvalidData(String),
+    /// Reading from or writing to a file, the terminal or the network failed.
+    Io {
+        message: String,
+        source: Box<dyn ErrorTrait>,
+    },
+    /// The user quit an interactive prompt, or its input ended before an answer was given.
+    Cancelled,
+    /// Anything else, such as invalid command-line arguments.
+    Other(String),
+}
+
+impl Error {
+    pub fn new<T: ?Sized>(message: &T) -> Error
+    where
+        T: ToString,
+    {
+        Error::Other(message.to_string())
+    }
+
+    pub fn parse<T: ?Sized + ToString>(message: &T) -> Error {
+        Error::Parse {
+            message: message.to_string(),
+            location: None,
+            source: None,
+        }
+    }
+
+    pub fn parse_caused_by<T: ?Sized + ToString, E: ErrorTrait + 'static>(
+        message: &T,
+        source: E,
+    ) -> Error {
+        Error::Parse {
+            message: message.to_string(),
+            location: None,
+            source: Some(Box::new(source)),
+        }
+    }
+
+    pub fn parse_at<T: ?Sized + ToString>(
+        message: &T,
+        location: Location,
+        source: Option<Box<dyn ErrorTrait>>,
+    ) -> Error {
+        Error::Parse {
+            message: message.to_string(),
+            location: Some(location),
+            source,
+        }
+    }
+
+    pub fn invalid_data<T: ?Sized + ToString>(message: &T) -> Error {
+        Error::InvalidData(message.to_string())
+    }
+
+    pub fn io<T: ?Sized + ToString, E: Into<Box<dyn ErrorTrait>>>(message: &T, source: E) -> Error {
+        Error::Io {
+            message: message.to_string(),
+            source: source.into(),
+        }
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parse {
+                message,
+                location: Some(location),
+                ..
+            } => write!(
+                f,
+                "{} at line {}, column {}\n{}",
+                message, location.line, location.column, location
+            ),
+            Error::Parse { message, .. } => write!(f, "{}", message),
+            Error::ChallengeNotFound {
+                year,
+                day,
+                location,
+            } => {
+                write!(
+                    f,
+                    "Challenge {} day {} not found in {}!",
+                    year, day, location
+                )
+            }
+            Error::SolutionNotFound { year, day, part } => {
+                write!(f, "No solution for {} day {} part {}!", year, day, part)
+            }
+            Error::InvalidData(message) => write!(f, "{}", message),
+            Error::Io { message, .. } => write!(f, "{}", message),
+            Error::Cancelled => write!(f, "Cancelled."),
+            Error::Other(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+impl ErrorTrait for Error {
+    fn source(&self) -> Option<&(dyn ErrorTrait + 'static)> {
+        match self {
+            Error::Parse { source, .. } => source.as_deref(),
+            Error::Io { source, .. } => Some(source.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+/// The error's message with the messages of its sources appended to its first line, ahead of
+/// any input excerpt that follows.
+pub fn describe_error(err: &dyn ErrorTrait) -> String {
+    let message = err.to_string();
+    let (mut headline, excerpt) = match message.split_once('\n') {
+        Some((headline, excerpt)) => (headline.to_string(), Some(excerpt)),
+        None => (message.clone(), None),
+    };
+    let mut source = err.source();
+    while let Some(cause) = source {
+        headline = format!("{} ({})", headline, cause);
+        source = cause.source();
+    }
+    match excerpt {
+        Some(excerpt) => format!("{}\n{}", headline, excerpt),
+        None => headline,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn sources_are_chained() {
+        let cause = u32::from_str("x").unwrap_err();
+        let err = Error::parse_caused_by("Invalid number x!", cause.clone());
+        assert_eq!("Invalid number x!", err.to_string());
+        assert_eq!(cause.to_string(), err.source().unwrap().to_string());
+        assert!(Error::invalid_data("No start cave!").source().is_none());
+        assert_eq!(
+            format!("Invalid number x! ({})", cause),
+            describe_error(&err)
+        );
+    }
+
+    #[test]
+    fn boxed_errors_keep_their_kind() {
+        let boxed: Box<dyn ErrorTrait> = Box::new(Error::SolutionNotFound {
+            year: 2021,
+            day: 3,
+            part: 2,
+        });
+        assert!(matches!(
+            boxed.downcast_ref::<Error>(),
+            Some(Error::SolutionNotFound {
+                day: 3,
+                part: 2,
+                ..
+            })
+        ));
+        assert_eq!("No solution for 2021 day 3 part 2!", boxed.to_string());
+    }
+}
@@ -0,0 +1,55 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+
+/// Runs `work` on every item on up to `jobs` threads, which take the next unclaimed item as they
+/// become free. The results come back in the order of `items`, however the work was scheduled.
+pub fn run_in_order<T, R, F>(items: &[T], jobs: usize, work: F) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync,
+{
+    let workers = jobs.min(items.len());
+    if workers <= 1 {
+        return items.iter().map(work).collect();
+    }
+    let next = AtomicUsize::new(0);
+    let slots: Mutex<Vec<Option<R>>> = Mutex::new(items.iter().map(|_| None).collect());
+    thread::scope(|scope| {
+        for _ in 0..workers {
+            scope.spawn(|| loop {
+                let index = next.fetch_add(1, Ordering::Relaxed);
+                let Some(item) = items.get(index) else {
+                    break;
+                };
+                let result = work(item);
+                slots.lock().unwrap()[index] = Some(result);
+            });
+        }
+    });
+    slots
+        .into_inner()
+        .unwrap()
+        .into_iter()
+        .map(|slot| slot.expect("Every item is taken by a worker"))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn results_keep_the_order_of_the_items() {
+        let items: Vec<u64> = (0..20).collect();
+        let results = run_in_order(&items, 4, |&n| {
+            // Make the early items finish last.
+            thread::sleep(Duration::from_millis(20 - n));
+            n * n
+        });
+        assert_eq!(items.iter().map(|n| n * n).collect::<Vec<u64>>(), results);
+        assert_eq!(vec![2, 4], run_in_order(&[1, 2], 1, |n| n * 2));
+    }
+}
@@ -2,13 +2,12 @@ mod cursor;
 mod value;
 
 use std::cell::RefCell;
-use std::error::Error;
 use std::fmt::{Display, Formatter};
 use std::iter::{repeat, repeat_with};
-use std::num::ParseIntError;
-use std::str::FromStr;
 
+use super::parsing::{grid, int, parse_input, Integer, Parse};
 use super::vector::Vector2;
+use super::Result;
 pub use cursor::Cursor;
 pub use value::Value;
 
@@ -51,25 +50,10 @@ impl<T: Value> Map2D<T> {
     }
 }
 
-impl<T: Value + FromStr<Err = ParseIntError>> FromStr for Map2D<T>
-where
-    Vec<T>: FromIterator<T>,
-{
-    type Err = Box<dyn Error>;
-
-    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
-        let data_values = s
-            .lines()
-            .map(|line| {
-                line.chars()
-                    .map(|c| T::from_str(&String::from(c)))
-                    .collect::<std::result::Result<Vec<T>, ParseIntError>>()
-            })
-            .filter(|res| match res {
-                Ok(nums) => !nums.is_empty(),
-                Err(_) => true,
-            })
-            .collect::<std::result::Result<Vec<Vec<T>>, ParseIntError>>()?;
+impl<T: Value + Integer> Parse for Map2D<T> {
+    /// Reads a grid of single digits.
+    fn parse(s: &str) -> Result<Self> {
+        let data_values = parse_input(s, grid(int::<T>()))?;
         Ok(Map2D {
             data: data_values
                 .into_iter()
@@ -84,6 +68,15 @@ where
     }
 }
 
+impl<T: Value> Clone for Map2D<T> {
+    fn clone(&self) -> Self {
+        Map2D {
+            data: self.data.clone(),
+            cursor_factory: Self::cursor_factory_default(),
+        }
+    }
+}
+
 impl<T: Value> Map2D<T> {
     pub fn width(&self) -> u64 {
         match self.data.first() {
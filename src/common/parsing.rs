@@ -0,0 +1,110 @@
+mod combinators;
+
+use core::result::Result as CoreResult;
+use std::error::Error as ErrorTrait;
+use std::fmt::{Display, Formatter};
+
+use super::{Error, Result};
+pub use combinators::{
+    grid, header, int, lines, pair, parse_input, sections, separated, single_line, word, words,
+    Integer,
+};
+
+/// Puzzle input that can be read from a challenge's raw data.
+pub trait Parse: Sized {
+    fn parse(input: &str) -> Result<Self>;
+}
+
+/// Where a parse error happened: the whole line, and the 1-based column and width of the
+/// offending text within it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Location {
+    pub line: usize,
+    pub column: usize,
+    pub width: usize,
+    pub text: String,
+}
+
+impl Display for Location {
+    /// Renders the line with carets under the offending text.
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let number = self.line.to_string();
+        let gutter = " ".repeat(number.len());
+        writeln!(f, "{} | {}", number, self.text)?;
+        write!(
+            f,
+            "{} | {}{}",
+            gutter,
+            " ".repeat(self.column - 1),
+            "^".repeat(self.width)
+        )
+    }
+}
+
+/// A line of puzzle input that knows its line number, so errors can point into it.
+#[derive(Clone, Copy, Debug)]
+pub struct InputLine<'a> {
+    pub number: usize,
+    pub text: &'a str,
+}
+
+impl<'a> InputLine<'a> {
+    pub fn new(number: usize, text: &'a str) -> InputLine<'a> {
+        InputLine { number, text }
+    }
+
+    /// The location of `part`, which must be a slice of this line's text.
+    pub fn location_of(&self, part: &str) -> Location {
+        let start = self.text.as_ptr() as usize;
+        let offset = (part.as_ptr() as usize)
+            .saturating_sub(start)
+            .min(self.text.len());
+        Location {
+            line: self.number,
+            column: self.text[..offset].chars().count() + 1,
+            width: part.chars().count().max(1),
+            text: self.text.to_string(),
+        }
+    }
+
+    /// A parse error pointing at `part`, which must be a slice of this line's text.
+    pub fn error<T: ?Sized + ToString>(&self, part: &str, message: &T) -> Error {
+        Error::parse_at(message, self.location_of(part), None)
+    }
+
+    /// Parses `part`, which must be a slice of this line's text, with `parser`, reporting where
+    /// it was on failure.
+    pub fn parse_with<T, E, F>(&self, part: &str, parser: F) -> Result<T>
+    where
+        E: ErrorTrait + 'static,
+        F: FnOnce(&str) -> CoreResult<T, E>,
+    {
+        match parser(part) {
+            Ok(value) => Ok(value),
+            Err(err) => Err(Box::new(Error::parse_at(
+                &format!("Could not parse \"{}\"", part),
+                self.location_of(part),
+                Some(Box::new(err)),
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn errors_point_at_the_offending_text() {
+        let line = InputLine::new(12, "0,9 -> 5x,9");
+        let err = line
+            .parse_with(&line.text[7..9], i32::from_str)
+            .unwrap_err();
+        assert_eq!(
+            "Could not parse \"5x\" at line 12, column 8\n12 | 0,9 -> 5x,9\n   |        ^^",
+            err.to_string()
+        );
+        assert!(err.source().is_some());
+    }
+}
@@ -0,0 +1,245 @@
+//! Building blocks for puzzle input formats. Line parsers take a line and a slice of its text,
+//! so every error can point at the offending text; multi-line parsers take a run of numbered
+//! lines, blank ones included, so they can split it into sections.
+use core::result::Result as CoreResult;
+use std::num::ParseIntError;
+
+use super::InputLine;
+use crate::{Error, Result};
+
+/// Integer types that can be parsed in any radix.
+pub trait Integer: Sized {
+    fn from_str_radix(s: &str, radix: u32) -> CoreResult<Self, ParseIntError>;
+}
+
+macro_rules! impl_integer {
+    ($($t:ty),*) => {
+        $(impl Integer for $t {
+            fn from_str_radix(s: &str, radix: u32) -> CoreResult<Self, ParseIntError> {
+                <$t>::from_str_radix(s, radix)
+            }
+        })*
+    };
+}
+
+impl_integer!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+/// Parses a whole puzzle input with `parser`, numbering its lines from 1.
+pub fn parse_input<T>(input: &str, parser: impl Fn(&[InputLine]) -> Result<T>) -> Result<T> {
+    let lines: Vec<InputLine> = input
+        .lines()
+        .enumerate()
+        .map(|(i, text)| InputLine::new(i + 1, text))
+        .collect();
+    parser(&lines)
+}
+
+/// A decimal integer, signed or unsigned depending on `T`.
+pub fn int<T: Integer>() -> impl Fn(&InputLine, &str) -> Result<T> {
+    radix(10)
+}
+
+/// An integer in the given radix, such as 2 for binary digits.
+pub fn radix<T: Integer>(radix: u32) -> impl Fn(&InputLine, &str) -> Result<T> {
+    move |line: &InputLine, text: &str| {
+        line.parse_with(text.trim(), |s| T::from_str_radix(s, radix))
+    }
+}
+
+/// A single word, without the whitespace around it.
+pub fn word() -> impl Fn(&InputLine, &str) -> Result<String> {
+    |line: &InputLine, text: &str| {
+        let text = text.trim();
+        if text.is_empty() || text.contains(char::is_whitespace) {
+            Err(Box::new(line.error(text, "Expected a single word")))
+        } else {
+            Ok(String::from(text))
+        }
+    }
+}
+
+/// Items separated by `separator`, such as `3,4,3,1,2`.
+pub fn separated<T>(
+    separator: &'static str,
+    item: impl Fn(&InputLine, &str) -> Result<T>,
+) -> impl Fn(&InputLine, &str) -> Result<Vec<T>> {
+    move |line: &InputLine, text: &str| {
+        text.trim()
+            .split(separator)
+            .map(|piece| item(line, piece))
+            .collect()
+    }
+}
+
+/// Items separated by any amount of whitespace.
+pub fn words<T>(
+    item: impl Fn(&InputLine, &str) -> Result<T>,
+) -> impl Fn(&InputLine, &str) -> Result<Vec<T>> {
+    move |line: &InputLine, text: &str| text.split_whitespace().map(|w| item(line, w)).collect()
+}
+
+/// Two items around the first `separator`, as in `a -> b`, `a-b` or `a | b`.
+pub fn pair<A, B>(
+    first: impl Fn(&InputLine, &str) -> Result<A>,
+    separator: &'static str,
+    second: impl Fn(&InputLine, &str) -> Result<B>,
+) -> impl Fn(&InputLine, &str) -> Result<(A, B)> {
+    move |line: &InputLine, text: &str| match text.split_once(separator) {
+        Some((a, b)) => Ok((first(line, a)?, second(line, b)?)),
+        None => Err(Box::new(
+            line.error(text, &format!("Expected \"{}\"", separator)),
+        )),
+    }
+}
+
+/// One item per non-blank line.
+pub fn lines<T>(
+    item: impl Fn(&InputLine, &str) -> Result<T>,
+) -> impl Fn(&[InputLine]) -> Result<Vec<T>> {
+    move |lines: &[InputLine]| non_blank(lines).map(|line| item(line, line.text)).collect()
+}
+
+/// Exactly one non-blank line.
+pub fn single_line<T>(
+    item: impl Fn(&InputLine, &str) -> Result<T>,
+) -> impl Fn(&[InputLine]) -> Result<T> {
+    move |lines: &[InputLine]| {
+        let mut lines = non_blank(lines);
+        match (lines.next(), lines.next()) {
+            (Some(line), None) => item(line, line.text),
+            (Some(_), Some(extra)) => Err(Box::new(
+                extra.error(extra.text, "Expected nothing after the first line"),
+            )),
+            (None, _) => Err(Box::new(Error::parse("No data found in the input!"))),
+        }
+    }
+}
+
+/// One item per character, in rows of equal width.
+pub fn grid<T>(
+    cell: impl Fn(&InputLine, &str) -> Result<T>,
+) -> impl Fn(&[InputLine]) -> Result<Vec<Vec<T>>> {
+    move |lines: &[InputLine]| {
+        let mut rows: Vec<Vec<T>> = vec![];
+        for line in non_blank(lines) {
+            let row = line
+                .text
+                .char_indices()
+                .map(|(i, c)| cell(line, &line.text[i..i + c.len_utf8()]))
+                .collect::<Result<Vec<T>>>()?;
+            if let Some(first) = rows.first() {
+                if row.len() != first.len() {
+                    return Err(Box::new(line.error(
+                        line.text,
+                        &format!(
+                            "Expected a row of {} cells, found {}",
+                            first.len(),
+                            row.len()
+                        ),
+                    )));
+                }
+            }
+            rows.push(row);
+        }
+        Ok(rows)
+    }
+}
+
+/// Runs of non-blank lines separated by blank lines, each parsed by `section`.
+pub fn sections<T>(
+    section: impl Fn(&[InputLine]) -> Result<T>,
+) -> impl Fn(&[InputLine]) -> Result<Vec<T>> {
+    move |lines: &[InputLine]| {
+        lines
+            .split(|line| is_blank(line))
+            .filter(|run| !run.is_empty())
+            .map(|run| section(run))
+            .collect()
+    }
+}
+
+/// The first section parsed by `head`, and every line after the blank line that ends it parsed
+/// by `body`.
+pub fn header<H, B>(
+    head: impl Fn(&[InputLine]) -> Result<H>,
+    body: impl Fn(&[InputLine]) -> Result<B>,
+) -> impl Fn(&[InputLine]) -> Result<(H, B)> {
+    move |lines: &[InputLine]| {
+        let start = lines
+            .iter()
+            .position(|line| !is_blank(line))
+            .unwrap_or(lines.len());
+        let end = lines[start..]
+            .iter()
+            .position(is_blank)
+            .map_or(lines.len(), |offset| start + offset);
+        Ok((head(&lines[start..end])?, body(&lines[end..])?))
+    }
+}
+
+fn non_blank<'a, 'b>(lines: &'a [InputLine<'b>]) -> impl Iterator<Item = &'a InputLine<'b>> {
+    lines.iter().filter(|line| !is_blank(line))
+}
+
+fn is_blank(line: &InputLine) -> bool {
+    line.text.trim().is_empty()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lists_and_pairs_are_parsed() -> Result<()> {
+        assert_eq!(
+            vec![3, -4, 3],
+            parse_input("3,-4,3\n", single_line(separated(",", int::<i32>())))?
+        );
+        assert_eq!(
+            vec![(String::from("start"), String::from("A"))],
+            parse_input("start-A", lines(pair(word(), "-", word())))?
+        );
+        assert_eq!(
+            vec![vec![1, 0], vec![0, 1]],
+            parse_input("10\n01", grid(radix::<u8>(2)))?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn sections_split_on_blank_lines() -> Result<()> {
+        let (numbers, boards) = parse_input(
+            "7,4\n\n1 2\n3 4\n\n\n5 6\n",
+            header(
+                single_line(separated(",", int::<u32>())),
+                sections(lines(words(int::<u32>()))),
+            ),
+        )?;
+        assert_eq!(vec![7, 4], numbers);
+        assert_eq!(vec![vec![vec![1, 2], vec![3, 4]], vec![vec![5, 6]]], boards);
+        Ok(())
+    }
+
+    #[test]
+    fn errors_point_at_the_bad_item() {
+        let err = parse_input("28\n\n14x3\n192", lines(int::<i32>()))
+            .err()
+            .unwrap();
+        assert_eq!(
+            "Could not parse \"14x3\" at line 3, column 1\n3 | 14x3\n  | ^^^^",
+            err.to_string()
+        );
+        let err = parse_input("forward 5\nup3", lines(pair(word(), " ", int::<i32>())))
+            .err()
+            .unwrap();
+        assert_eq!(
+            "Expected \" \" at line 2, column 1\n2 | up3\n  | ^^^",
+            err.to_string()
+        );
+        let err = parse_input("12\n345", grid(int::<u8>())).err().unwrap();
+        assert_eq!(
+            "Expected a row of 2 cells, found 3 at line 2, column 1\n2 | 345\n  | ^^^",
+            err.to_string()
+        );
+    }
+}
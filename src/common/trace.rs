@@ -0,0 +1,68 @@
+use std::fmt::{Arguments, Display};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static TRACING: AtomicBool = AtomicBool::new(false);
+
+/// Receives the intermediate states of a simulation. Models take a generic `T: Tracer`, so when
+/// they are given a `NoTracer` the calls, and the formatting of their arguments, compile away.
+pub trait Tracer {
+    fn trace(&mut self, event: Arguments, state: &dyn Display);
+}
+
+pub struct NoTracer;
+
+impl Tracer for NoTracer {
+    #[inline(always)]
+    fn trace(&mut self, _event: Arguments, _state: &dyn Display) {}
+}
+
+/// Writes each state to stderr, so traces don't get mixed up with the answers on stdout.
+pub struct StderrTracer;
+
+impl Tracer for StderrTracer {
+    fn trace(&mut self, event: Arguments, state: &dyn Display) {
+        eprintln!("== {} ==\n{}", event, state);
+    }
+}
+
+/// Keeps every event and state as text, for checking what a model traced.
+#[cfg(test)]
+#[derive(Default)]
+pub struct RecordingTracer {
+    pub events: Vec<(String, String)>,
+}
+
+#[cfg(test)]
+impl Tracer for RecordingTracer {
+    fn trace(&mut self, event: Arguments, state: &dyn Display) {
+        self.events.push((event.to_string(), state.to_string()));
+    }
+}
+
+pub fn enable_tracing() {
+    TRACING.store(true, Ordering::Relaxed);
+}
+
+/// The tracer solutions should report to: `Some` only when `--trace` was given.
+pub fn active_tracer() -> Option<StderrTracer> {
+    TRACING.load(Ordering::Relaxed).then_some(StderrTracer)
+}
+
+/// Evaluates `$body` with `$tracer` bound to the active tracer, or to `NoTracer` when tracing is
+/// off. The body is compiled once per tracer type, so the untraced path pays nothing.
+macro_rules! with_tracer {
+    ($tracer:ident => $body:expr) => {
+        match $crate::common::trace::active_tracer() {
+            Some(mut active) => {
+                let $tracer = &mut active;
+                $body
+            }
+            None => {
+                let $tracer = &mut $crate::common::trace::NoTracer;
+                $body
+            }
+        }
+    };
+}
+
+pub(crate) use with_tracer;
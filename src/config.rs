@@ -0,0 +1,261 @@
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use serde::Deserialize;
+
+use crate::aoc::CHALLENGES_ENV_VAR;
+use crate::cli::{ALL_CHALLENGE_DAYS, FIRST_YEAR};
+use crate::common::{AllDayParams, DayParams, OutputFormat};
+use crate::{Error, Result};
+
+/// The names the config file may have, in the project root or the user's config directory.
+pub const CONFIG_FILE_NAMES: [&str; 2] = ["aoc.toml", "aoc.json"];
+const CONFIG_DIR_NAME: &str = "aoc2021";
+
+/// Defaults for the command-line options, so they don't have to be typed every time. Options
+/// given on the command line win.
+#[derive(Debug, Default, PartialEq)]
+pub struct Config {
+    /// Like `--challenges`. Ignored when `AOC2021_CHALLENGES` is set.
+    pub challenges_path: Option<PathBuf>,
+    pub dataset: Option<DefaultDataset>,
+    pub output_format: Option<OutputFormat>,
+    pub color: Option<bool>,
+    pub jobs: Option<usize>,
+    pub day_params: AllDayParams,
+}
+
+/// Which input to run on when the command line doesn't say.
+#[derive(Clone, Debug, PartialEq)]
+pub enum DefaultDataset {
+    Puzzle,
+    FirstExample,
+    Example(String),
+}
+
+impl From<&str> for DefaultDataset {
+    fn from(name: &str) -> Self {
+        match name {
+            "puzzle" => DefaultDataset::Puzzle,
+            "example" => DefaultDataset::FirstExample,
+            name => DefaultDataset::Example(String::from(name)),
+        }
+    }
+}
+
+/// The config file as written, before its values are checked.
+#[derive(Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct ConfigFile {
+    challenges: Option<PathBuf>,
+    dataset: Option<String>,
+    format: Option<String>,
+    color: Option<bool>,
+    jobs: Option<usize>,
+    /// Settings by year, then day, then name.
+    params: BTreeMap<String, BTreeMap<String, DayParams>>,
+}
+
+impl Config {
+    /// Checks the values in `file`, which was read from `dir`. Relative paths are taken to be
+    /// relative to `dir`.
+    fn from_file(file: ConfigFile, dir: &Path) -> Result<Config> {
+        if file.jobs == Some(0) {
+            return Err(Box::new(Error::new("jobs must be at least 1!")));
+        }
+        let mut day_params = AllDayParams::new();
+        for (year_str, days) in file.params {
+            let year = match u16::from_str(&year_str) {
+                Ok(year) if year >= FIRST_YEAR => year,
+                _ => {
+                    return Err(Box::new(Error::new(&format!(
+                        "params.{} is not a year from {} on!",
+                        year_str, FIRST_YEAR
+                    ))))
+                }
+            };
+            for (day_str, params) in days {
+                let day = match u8::from_str(&day_str) {
+                    Ok(day) if ALL_CHALLENGE_DAYS.contains(&(day as u32)) => day,
+                    _ => {
+                        return Err(Box::new(Error::new(&format!(
+                            "params.{}.{} is not a day from 1 to 25!",
+                            year_str, day_str
+                        ))))
+                    }
+                };
+                day_params.insert((year, day), params);
+            }
+        }
+        Ok(Config {
+            challenges_path: file.challenges.map(|path| dir.join(path)),
+            dataset: file.dataset.as_deref().map(DefaultDataset::from),
+            output_format: file
+                .format
+                .as_deref()
+                .map(OutputFormat::from_str)
+                .transpose()?,
+            color: file.color,
+            jobs: file.jobs,
+            day_params,
+        })
+    }
+
+    /// Fills in anything this config leaves out from `fallback`. Day settings are merged one
+    /// name at a time.
+    fn or(self, fallback: Config) -> Config {
+        let mut day_params = fallback.day_params;
+        for (key, params) in self.day_params {
+            day_params.entry(key).or_default().extend(params);
+        }
+        Config {
+            challenges_path: self.challenges_path.or(fallback.challenges_path),
+            dataset: self.dataset.or(fallback.dataset),
+            output_format: self.output_format.or(fallback.output_format),
+            color: self.color.or(fallback.color),
+            jobs: self.jobs.or(fallback.jobs),
+            day_params,
+        }
+    }
+}
+
+/// Reads the config files in the user's config directory and the current directory, the root of
+/// the project. Settings in the project's file win. Having no config file is fine.
+pub fn load_config() -> Result<Config> {
+    let mut config = Config::default();
+    let dirs = user_config_dir().into_iter().chain([PathBuf::from(".")]);
+    for dir in dirs {
+        if let Some(found) = read_config_in(&dir)? {
+            config = found.or(config);
+        }
+    }
+    if env::var_os(CHALLENGES_ENV_VAR).is_some() {
+        config.challenges_path = None;
+    }
+    Ok(config)
+}
+
+fn user_config_dir() -> Option<PathBuf> {
+    let config_home = match env::var_os("XDG_CONFIG_HOME").filter(|dir| !dir.is_empty()) {
+        Some(config_home) => PathBuf::from(config_home),
+        None => Path::new(&env::var_os("HOME")?).join(".config"),
+    };
+    Some(config_home.join(CONFIG_DIR_NAME))
+}
+
+fn read_config_in(dir: &Path) -> Result<Option<Config>> {
+    let paths: Vec<PathBuf> = CONFIG_FILE_NAMES
+        .iter()
+        .map(|name| dir.join(name))
+        .filter(|path| path.is_file())
+        .collect();
+    let path = match paths.as_slice() {
+        [] => return Ok(None),
+        [path] => path,
+        _ => {
+            return Err(Box::new(Error::new(&format!(
+                "Found both {} and {} in {}; keep only one of them!",
+                CONFIG_FILE_NAMES[0],
+                CONFIG_FILE_NAMES[1],
+                dir.display()
+            ))))
+        }
+    };
+    let text = fs::read_to_string(path).map_err(|err| {
+        Error::io(
+            &format!("Could not read the config file {}!", path.display()),
+            err,
+        )
+    })?;
+    let config = parse_config(&text, path).map_err(|err| {
+        Error::new(&format!(
+            "Invalid config file {}: {}",
+            path.display(),
+            err.to_string().trim_end()
+        ))
+    })?;
+    Ok(Some(config))
+}
+
+/// Parses a config file as TOML or JSON, going by the extension of `path`.
+fn parse_config(text: &str, path: &Path) -> Result<Config> {
+    let file: ConfigFile = match path.extension().and_then(|ext| ext.to_str()) {
+        Some("json") => serde_json::from_str(text)?,
+        _ => toml::from_str(text)?,
+    };
+    Config::from_file(file, path.parent().unwrap_or(Path::new("")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn toml_and_json_configs_are_read() -> Result<()> {
+        let toml = parse_config(
+            r#"
+            challenges = "data/private.json"
+            dataset = "larger"
+            format = "json"
+            color = false
+            jobs = 4
+
+            [params.2021.6]
+            part_1_days = 18
+            "#,
+            Path::new("aoc.toml"),
+        )?;
+        let json = parse_config(
+            r#"{"challenges": "data/private.json", "dataset": "larger", "format": "json",
+                "color": false, "jobs": 4, "params": {"2021": {"6": {"part_1_days": 18}}}}"#,
+            Path::new("aoc.json"),
+        )?;
+        assert_eq!(toml, json);
+        assert_eq!(
+            Some(PathBuf::from("data/private.json")),
+            toml.challenges_path
+        );
+        assert_eq!(
+            Some(DefaultDataset::Example(String::from("larger"))),
+            toml.dataset
+        );
+        assert_eq!(Some(OutputFormat::Json), toml.output_format);
+        assert_eq!(Some(&18), toml.day_params[&(2021, 6)].get("part_1_days"));
+
+        let user = parse_config(
+            "dataset = \"example\"\n[params.2021.6]\npart_2_days = 100\npart_1_days = 80",
+            Path::new("/home/me/.config/aoc2021/aoc.toml"),
+        )?;
+        let merged = toml.or(user);
+        assert_eq!(
+            Some(DefaultDataset::Example(String::from("larger"))),
+            merged.dataset
+        );
+        assert_eq!(
+            Some(&DayParams::from([
+                (String::from("part_1_days"), 18),
+                (String::from("part_2_days"), 100)
+            ])),
+            merged.day_params.get(&(2021, 6))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn invalid_configs_are_rejected() {
+        let path = Path::new("aoc.toml");
+        for text in [
+            "jobs = 0",
+            "format = \"xml\"",
+            "colour = true",
+            "[params.2021.26]\nsteps = 1",
+            "[params.21.1]\nsteps = 1",
+            "[params.2021.1]\nsteps = -1",
+        ] {
+            assert!(parse_config(text, path).is_err(), "{}", text);
+        }
+    }
+}
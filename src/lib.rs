@@ -1,16 +1,21 @@
 mod aoc;
+mod cli;
 mod common;
+mod config;
 mod prompt;
 mod sub;
+mod terminal;
 
-use std::env::Args;
-use std::iter::Peekable;
-use std::str::FromStr;
+use std::ops::RangeInclusive;
 
+use cli::ALL_CHALLENGE_DAYS;
+pub use cli::{parse_args, USAGE};
 use common::AppParams;
+use common::Command;
 use common::Error;
 use common::Result;
-use prompt::prompt_select_from_numbered_list;
+pub use config::{load_config, Config};
+use prompt::{prompt_select_from_numbered_list, Prompter, TerminalPrompter};
 
 enum ChallengeOption {
     DisplayExampleData,
@@ -19,22 +24,122 @@ enum ChallengeOption {
 }
 
 pub fn run(app_params: AppParams) -> Result<()> {
+    match &app_params.command {
+        Command::Help => {
+            println!("{}", USAGE);
+            return Ok(());
+        }
+        Command::Import(import) => return aoc::run_import(&app_params, import),
+        Command::New(new) => return aoc::run_new(&app_params, new),
+        Command::Watch(watch) => return aoc::run_watch(&app_params, watch),
+        Command::Verify if app_params.input.is_some() => {
+            return Err(Box::new(Error::new("--input cannot be used with verify!")))
+        }
+        _ if app_params.input.is_some() && app_params.batch_days.is_some() => {
+            return Err(Box::new(Error::new(
+                "--input can only be used with a single --day!",
+            )))
+        }
+        _ => {}
+    }
+    if app_params.trace {
+        common::trace::enable_tracing();
+    }
+    common::day_params::set_day_params(&app_params.day_params);
+    let mut challenges = aoc::open_challenge_source(&app_params)?;
+    match &app_params.command {
+        Command::List => aoc::run_list(
+            challenges.as_ref(),
+            app_params.year,
+            selected_days(&app_params),
+        ),
+        Command::Show(day) => aoc::run_show(
+            challenges.as_ref(),
+            aoc::ChallengeKey::new(app_params.year, *day as u8),
+            &app_params,
+        ),
+        Command::Run => aoc::run_batch_with_summary(
+            challenges.as_ref(),
+            selected_days(&app_params),
+            &app_params,
+        ),
+        Command::Bench(iterations) => aoc::run_bench_with_report(
+            challenges.as_ref(),
+            selected_days(&app_params),
+            *iterations,
+            &app_params,
+        ),
+        Command::Verify => aoc::run_verification_with_summary(
+            challenges.as_ref(),
+            app_params.year,
+            selected_days(&app_params),
+            app_params.challenge_part.map(|part| part as u8),
+            app_params.jobs,
+        ),
+        Command::Fetch(fetch) => aoc::run_fetch(challenges.as_mut(), app_params.year, fetch),
+        Command::Submit(submit) => aoc::run_submit(challenges.as_ref(), &app_params, submit),
+        Command::Help | Command::Import(_) | Command::New(_) | Command::Watch(_) => Ok(()),
+        Command::Calendar => aoc::run_calendar(challenges.as_ref(), &app_params),
+        Command::Interactive => {
+            run_interactive(challenges.as_ref(), &app_params, &mut TerminalPrompter)
+        }
+    }
+}
+
+fn run_interactive(
+    challenges: &dyn aoc::ChallengeSource,
+    app_params: &AppParams,
+    prompter: &mut dyn Prompter,
+) -> Result<()> {
+    if let Some(days) = &app_params.batch_days {
+        return aoc::run_batch_with_summary(
+            challenges,
+            *days.start() as u8..=*days.end() as u8,
+            app_params,
+        );
+    }
+    if app_params.input.is_some() {
+        // Prompts would compete with the input for stdin, so run every part non-interactively.
+        return match app_params.challenge_day {
+            Some(day) => aoc::run_batch_with_summary(challenges, day as u8..=day as u8, app_params),
+            None => Err(Box::new(Error::new("--input requires --day!"))),
+        };
+    }
+
+    match run_menu(challenges, app_params, prompter) {
+        Err(err) if prompt::is_cancelled(err.as_ref()) => {
+            prompter.tell(&err.to_string());
+            Ok(())
+        }
+        result => result,
+    }
+}
+
+/// Asks for the day, the part and what to do with them, for whatever wasn't given as options.
+fn run_menu(
+    challenges: &dyn aoc::ChallengeSource,
+    app_params: &AppParams,
+    prompter: &mut dyn Prompter,
+) -> Result<()> {
     let challenge_day: u8 = match app_params.challenge_day {
         Some(day) => day as u8,
-        None => aoc::prompt_select_challenge_day()?,
+        None => aoc::prompt_select_challenge_day(prompter, app_params.year)?,
     };
-    let challenge = aoc::get_challenge(challenge_day)?;
-    println!("Loaded challenge for day {}.", challenge.day);
+    let challenge = aoc::get_challenge(
+        challenges,
+        aoc::ChallengeKey::new(app_params.year, challenge_day),
+    )?;
+    prompter.tell(&format!("Loaded challenge for {}.", challenge.key()));
     let challenge_part: u8 = if challenge.parts > 1 {
         match app_params.challenge_part {
             Some(part) => part as u8,
-            None => aoc::prompt_select_challenge_part(challenge.parts)?,
+            None => aoc::prompt_select_challenge_part(prompter, challenge.parts)?,
         }
     } else {
         1
     };
 
-    let challenge_solution = aoc::get_challenge_solution(challenge.day, challenge_part);
+    let challenge_solution = aoc::solution_registry()?.get_part(challenge.key(), challenge_part);
 
     let mut options: Vec<ChallengeOption> = vec![
         ChallengeOption::DisplayExampleData,
@@ -46,65 +151,102 @@ pub fn run(app_params: AppParams) -> Result<()> {
         captions.insert(0, "Run challenge solution");
     }
     let challenge_option =
-        prompt_select_from_numbered_list("What do you want to do?", &options, &captions)?;
+        prompt_select_from_numbered_list(prompter, "What do you want to do?", &options, &captions)?;
 
     match challenge_option {
         ChallengeOption::DisplayExampleData => {
-            println!("Challenge example data:\n{}", challenge.example_data)
+            for example in &challenge.examples {
+                prompter.tell(&format!(
+                    "Challenge example data ({}):\n{}",
+                    example.name, example.data
+                ))
+            }
+        }
+        ChallengeOption::DisplayRealData => {
+            prompter.tell(&format!("Challenge data:\n{}", challenge.data))
         }
-        ChallengeOption::DisplayRealData => println!("Challenge data:\n{}", challenge.data),
         ChallengeOption::RunSolution => {
             return match challenge_solution {
-                Some(solution) => solution.run(challenge, app_params),
-                None => Err(Box::new(Error::new(
-                    "Runtime error: Challenge solution not found!",
-                ))),
+                Some(solution) => {
+                    let answer = solution.run(&challenge, app_params, challenge_part)?;
+                    prompter.tell(&format!("Answer: {}", answer));
+                    Ok(())
+                }
+                None => Err(Box::new(Error::SolutionNotFound {
+                    year: challenge.year,
+                    day: challenge.day,
+                    part: challenge_part,
+                })),
             }
         }
     }
     Ok(())
 }
 
-pub fn parse_args(args: Args) -> Result<AppParams> {
-    let mut args = args.peekable();
-    let program_name = args.next().unwrap();
+fn selected_days(app_params: &AppParams) -> RangeInclusive<u8> {
+    match (&app_params.batch_days, app_params.challenge_day) {
+        (Some(days), _) => *days.start() as u8..=*days.end() as u8,
+        (None, Some(day)) => day as u8..=day as u8,
+        (None, None) => *ALL_CHALLENGE_DAYS.start() as u8..=*ALL_CHALLENGE_DAYS.end() as u8,
+    }
+}
 
-    let mut use_example_data = false;
-    let mut challenge_day: Option<u32> = None;
-    let mut challenge_part: Option<u32> = None;
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc::{Challenge, ChallengeKey};
+    use prompt::ScriptedPrompter;
 
-    while let Some(next_arg) = args.peek() {
-        match next_arg.as_str() {
-            "--day" => challenge_day = Some(consume_u32_option(&mut args)?),
-            "--part" => challenge_part = Some(consume_u32_option(&mut args)?),
-            "--use-example-data" => use_example_data = consume_boolean_option(&mut args),
-            s => return Err(Box::new(Error::new(&format!("Unrecognized option {}!", s)))),
-        };
-    }
+    const DEPTHS: &str = "199\n200\n208\n210\n200\n207\n240\n269\n260\n263";
 
-    Ok(AppParams {
-        program_name,
-        use_example_data,
-        challenge_day,
-        challenge_part,
-    })
-}
+    fn run_script(args: &[&str], answers: &[&str]) -> Result<Vec<String>> {
+        let app_params = parse_args(
+            ["aoc2021"].iter().chain(args).map(|arg| arg.to_string()),
+            &Config::default(),
+        )?;
+        let challenges = aoc::MemorySource::new().with_challenge(Challenge {
+            data: String::from(DEPTHS),
+            ..Challenge::new(ChallengeKey::new(2021, 1))
+        });
+        let mut prompter = ScriptedPrompter::new(answers);
+        run_interactive(&challenges, &app_params, &mut prompter)?;
+        Ok(prompter.transcript)
+    }
 
-fn consume_boolean_option(args: &mut Peekable<Args>) -> bool {
-    args.next();
-    true
-}
+    #[test]
+    fn menu_runs_the_chosen_solution() -> Result<()> {
+        let transcript = run_script(&[], &["1", "3", "2", "1"])?;
+        assert_eq!("Please select a challenge from 2021. (1-25)", transcript[0]);
+        assert_eq!("Loaded challenge for 2021 day 1.", transcript[1]);
+        assert_eq!(
+            "Please enter a valid number between 1 and 2.",
+            transcript[3]
+        );
+        assert_eq!(
+            "What do you want to do?\n1) Run challenge solution\n\
+             2) Show challenge example data\n3) Show challenge puzzle data",
+            transcript[5]
+        );
+        assert_eq!(Some(&String::from("Answer: 5")), transcript.last());
+        Ok(())
+    }
 
-fn consume_string_option(args: &mut Peekable<Args>) -> String {
-    args.next();
-    match args.next() {
-        Some(s) => s,
-        None => String::new(),
+    #[test]
+    fn options_skip_their_prompts() -> Result<()> {
+        let transcript = run_script(&["--day", "1", "--part", "1"], &["3"])?;
+        assert_eq!(3, transcript.len());
+        assert_eq!(format!("Challenge data:\n{}", DEPTHS), transcript[2]);
+        Ok(())
     }
-}
 
-fn consume_u32_option(args: &mut Peekable<Args>) -> Result<u32> {
-    let str_val = consume_string_option(args);
-    let u32_val = u32::from_str(&str_val)?;
-    Ok(u32_val)
+    #[test]
+    fn quitting_or_ending_the_input_stops_quietly() -> Result<()> {
+        assert_eq!(
+            Some(&String::from("Cancelled.")),
+            run_script(&[], &["1", "q"])?.last()
+        );
+        assert_eq!(3, run_script(&["--day", "1"], &[])?.len());
+        assert!(run_script(&[], &["2"]).is_err());
+        Ok(())
+    }
 }
@@ -1,10 +1,35 @@
-fn main() {
-    match aoc2021::parse_args(std::env::args()) {
-        Ok(app_params) => {
-            if let Err(message) = aoc2021::run(app_params) {
-                eprintln!("{}", message);
-            }
+use std::error::Error;
+use std::process::ExitCode;
+
+fn main() -> ExitCode {
+    let config = match aoc2021::load_config() {
+        Ok(config) => config,
+        Err(message) => {
+            eprintln!("{}", message);
+            return ExitCode::from(2);
         }
-        Err(message) => eprintln!("{}", message),
     };
+    let app_params = match aoc2021::parse_args(std::env::args(), &config) {
+        Ok(app_params) => app_params,
+        Err(message) => {
+            eprintln!("{}\nRun with --help for usage.", message);
+            return ExitCode::from(2);
+        }
+    };
+    match aoc2021::run(app_params) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            print_error_chain(err.as_ref());
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn print_error_chain(err: &dyn Error) {
+    eprintln!("{}", err);
+    let mut source = err.source();
+    while let Some(cause) = source {
+        eprintln!("  caused by: {}", cause);
+        source = cause.source();
+    }
 }
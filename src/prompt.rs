@@ -1,49 +1,122 @@
-use crate::{Error, Result};
-use std::io::{self, Write};
+#[cfg(test)]
+use std::collections::VecDeque;
+use std::io::{self, BufRead, Write};
 use std::str::FromStr;
 
+use crate::{Error, Result};
+
 macro_rules! invalid_number_msg {
     () => {
         "Please enter a valid number between {} and {}."
     };
 }
 
-pub fn prompt(message: &str) -> Result<String> {
-    let mut answer = String::new();
-    println!("{}", message);
-    print!("> ");
-    io::stdout().flush()?;
-    let mut prompt_result: io::Result<usize> = io::stdin().read_line(&mut answer);
-    while prompt_result.is_err() {
-        println!("Sorry, I didn't quite catch that! Try again.");
+/// Answers that stop the prompts instead of choosing something.
+const CANCEL_ANSWERS: [&str; 2] = ["q", "quit"];
+
+/// Where interactive questions are asked and answered, so menus can also be driven by a script.
+pub trait Prompter {
+    /// Shows `message` and returns the next answer without its line ending, or `None` when the
+    /// input has ended.
+    fn ask(&mut self, message: &str) -> Result<Option<String>>;
+
+    /// Shows `message` without waiting for an answer.
+    fn tell(&mut self, message: &str);
+}
+
+/// Asks on stdout and reads the answers from stdin.
+pub struct TerminalPrompter;
+
+impl Prompter for TerminalPrompter {
+    fn ask(&mut self, message: &str) -> Result<Option<String>> {
+        println!("{}", message);
         print!("> ");
-        prompt_result = io::stdin().read_line(&mut answer);
+        io::stdout().flush()?;
+        let mut answer = String::new();
+        match io::stdin().lock().read_line(&mut answer) {
+            Ok(0) => {
+                // Leave the terminal on a fresh line after Ctrl-D.
+                println!();
+                Ok(None)
+            }
+            Ok(_) => Ok(Some(answer.trim_end_matches(['\r', '\n']).to_string())),
+            Err(err) => Err(Box::new(Error::io(
+                "Could not read an answer to the prompt!",
+                err,
+            ))),
+        }
+    }
+
+    fn tell(&mut self, message: &str) {
+        println!("{}", message);
+    }
+}
+
+/// Answers prompts from a fixed list and records everything that was shown.
+#[cfg(test)]
+pub struct ScriptedPrompter {
+    answers: VecDeque<String>,
+    pub transcript: Vec<String>,
+}
+
+#[cfg(test)]
+impl ScriptedPrompter {
+    pub fn new(answers: &[&str]) -> ScriptedPrompter {
+        ScriptedPrompter {
+            answers: answers.iter().map(|answer| answer.to_string()).collect(),
+            transcript: vec![],
+        }
     }
-    match prompt_result {
-        Ok(_) => Ok(answer),
-        Err(err) => panic!("{}", err),
+}
+
+#[cfg(test)]
+impl Prompter for ScriptedPrompter {
+    fn ask(&mut self, message: &str) -> Result<Option<String>> {
+        self.transcript.push(message.to_string());
+        Ok(self.answers.pop_front())
+    }
+
+    fn tell(&mut self, message: &str) {
+        self.transcript.push(message.to_string());
     }
 }
 
-pub fn prompt_select_bounded_number(message: &str, min: u8, max: u8) -> Result<u8> {
-    let selection_str = prompt(message)?;
-    let parsed_number_result = u8::from_str(selection_str.trim());
-    if let Err(_) = parsed_number_result {
-        println!(invalid_number_msg!(), min, max);
-        return prompt_select_bounded_number(message, min, max);
+/// Whether `err` is the user cancelling a prompt rather than something going wrong.
+pub fn is_cancelled(err: &(dyn std::error::Error + 'static)) -> bool {
+    matches!(err.downcast_ref::<Error>(), Some(Error::Cancelled))
+}
+
+/// Asks `message` and returns the answer, failing with `Error::Cancelled` if the input ended or
+/// the user asked to quit.
+pub fn prompt(prompter: &mut dyn Prompter, message: &str) -> Result<String> {
+    match prompter.ask(message)? {
+        Some(answer) if !CANCEL_ANSWERS.contains(&answer.trim().to_lowercase().as_str()) => {
+            Ok(answer)
+        }
+        _ => Err(Box::new(Error::Cancelled)),
     }
-    let parsed_number = parsed_number_result.unwrap();
-    if parsed_number < min || parsed_number > max {
-        println!(invalid_number_msg!(), min, max);
-        return prompt_select_bounded_number(message, min, max);
+}
+
+/// Asks until the answer is a number from `min` to `max`.
+pub fn prompt_select_bounded_number(
+    prompter: &mut dyn Prompter,
+    message: &str,
+    min: usize,
+    max: usize,
+) -> Result<usize> {
+    loop {
+        match usize::from_str(prompt(prompter, message)?.trim()) {
+            Ok(number) if (min..=max).contains(&number) => return Ok(number),
+            _ => prompter.tell(&format!(invalid_number_msg!(), min, max)),
+        }
     }
-    return Ok(parsed_number);
 }
 
 pub fn prompt_select_from_numbered_list<'a, T>(
+    prompter: &mut dyn Prompter,
     message: &str,
-    options: &'a Vec<T>,
-    captions: &Vec<&str>,
+    options: &'a [T],
+    captions: &[&str],
 ) -> Result<&'a T> {
     let mut prompt_text = String::from(message);
 
@@ -51,9 +124,38 @@ pub fn prompt_select_from_numbered_list<'a, T>(
         prompt_text = format!("{}\n{}) {}", prompt_text, i + 1, caption);
     }
 
-    let action = prompt_select_bounded_number(&prompt_text, 1, options.len() as u8)?;
-    match options.get(action as usize - 1) {
+    let action = prompt_select_bounded_number(prompter, &prompt_text, 1, options.len())?;
+    match options.get(action - 1) {
         Some(option) => Ok(option),
         None => Err(Box::new(Error::new(&format!("Invalid choice {}!", action)))),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn invalid_answers_are_asked_again() -> Result<()> {
+        let mut prompter = ScriptedPrompter::new(&["", "abc", "0", "301", " 300 "]);
+        assert_eq!(
+            300,
+            prompt_select_bounded_number(&mut prompter, "How many?", 1, 300)?
+        );
+        assert_eq!(9, prompter.transcript.len());
+        assert_eq!(
+            "Please enter a valid number between 1 and 300.",
+            prompter.transcript[7]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn quitting_and_running_out_of_input_cancel() {
+        for answers in [&["Q"][..], &["x", "quit"], &[]] {
+            let mut prompter = ScriptedPrompter::new(answers);
+            let err = prompt_select_bounded_number(&mut prompter, "How many?", 1, 3).unwrap_err();
+            assert!(is_cancelled(err.as_ref()));
+        }
+    }
+}
@@ -2,52 +2,56 @@ mod bingo;
 pub mod board;
 
 pub use crate::sub::bingo::board::BingoBoard;
-use crate::Result;
 
-pub fn find_winning_bingo_boards(
+use crate::common::Tracer;
+use std::fmt::{Display, Formatter};
+
+pub fn find_winning_bingo_boards<T: Tracer>(
     random_numbers: Vec<u32>,
     mut boards: Vec<BingoBoard>,
-) -> Result<()> {
-    let winners = play_bingo_with_winning_boards(&random_numbers, &mut boards);
-    display_index_header("Winning board(s)", &winners);
-    display_boards_with_score_by_index(&winners, &boards);
-    Ok(())
+    tracer: &mut T,
+) -> Vec<BingoBoard> {
+    let winners = play_bingo_with_winning_boards(&random_numbers, &mut boards, tracer);
+    take_boards_by_index(&winners, boards)
 }
 
-pub fn find_losing_bingo_boards(
+pub fn find_losing_bingo_boards<T: Tracer>(
     random_numbers: Vec<u32>,
     mut boards: Vec<BingoBoard>,
-) -> Result<()> {
-    let losers = play_bingo_with_losing_boards(&random_numbers, &mut boards);
-    display_index_header("Losing board(s)", &losers);
-    display_boards_with_score_by_index(&losers, &boards);
-    Ok(())
+    tracer: &mut T,
+) -> Vec<BingoBoard> {
+    let losers = play_bingo_with_losing_boards(&random_numbers, &mut boards, tracer);
+    take_boards_by_index(&losers, boards)
 }
 
-fn display_index_header(caption: &str, indices: &Vec<usize>) {
-    println!(
-        "{}: {}",
-        caption,
-        indices
-            .iter()
-            .map(|i| format!("{}", i + 1))
-            .collect::<Vec<String>>()
-            .join(", ")
-    );
+/// Every board side by side in the trace, with called numbers marked.
+struct Boards<'a>(&'a [BingoBoard]);
+
+impl Display for Boards<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for board in self.0 {
+            writeln!(f, "{}", board)?;
+        }
+        Ok(())
+    }
 }
 
-fn display_boards_with_score_by_index(indices: &Vec<usize>, boards: &Vec<BingoBoard>) {
-    indices
-        .iter()
-        .for_each(|i| println!("Score: {}\n{}", boards[*i].get_score(), boards[*i]));
+fn take_boards_by_index(indices: &[usize], boards: Vec<BingoBoard>) -> Vec<BingoBoard> {
+    boards
+        .into_iter()
+        .enumerate()
+        .filter(|(i, _)| indices.contains(i))
+        .map(|(_, board)| board)
+        .collect()
 }
 
-fn play_bingo_with_winning_boards(
+fn play_bingo_with_winning_boards<T: Tracer>(
     random_numbers: &Vec<u32>,
     boards: &mut Vec<BingoBoard>,
+    tracer: &mut T,
 ) -> Vec<usize> {
     for n in random_numbers {
-        let winning_boards = call_returning_winning_boards(*n, boards);
+        let winning_boards = call_returning_winning_boards(*n, boards, tracer);
         if !winning_boards.is_empty() {
             return winning_boards;
         }
@@ -55,27 +59,100 @@ fn play_bingo_with_winning_boards(
     vec![]
 }
 
-fn play_bingo_with_losing_boards(
+fn play_bingo_with_losing_boards<T: Tracer>(
     random_numbers: &Vec<u32>,
     boards: &mut Vec<BingoBoard>,
+    tracer: &mut T,
 ) -> Vec<usize> {
     let mut winning_boards: Vec<usize> = Vec::new();
     for n in random_numbers {
-        winning_boards.append(&mut call_returning_winning_boards(*n, boards));
-        if winning_boards.len() >= boards.len() {
-            return vec![*winning_boards.last().unwrap()];
+        winning_boards.append(&mut call_returning_winning_boards(*n, boards, tracer));
+        if let Some(&last_winner) = winning_boards.last() {
+            if winning_boards.len() >= boards.len() {
+                return vec![last_winner];
+            }
         }
     }
     vec![]
 }
 
-fn call_returning_winning_boards(n: u32, boards: &mut Vec<BingoBoard>) -> Vec<usize> {
-    boards
+fn call_returning_winning_boards<T: Tracer>(
+    n: u32,
+    boards: &mut Vec<BingoBoard>,
+    tracer: &mut T,
+) -> Vec<usize> {
+    let winners: Vec<usize> = boards
         .iter_mut()
         .enumerate()
         .filter_map(|(i, board)| match board.call_number(n) {
             bingo::BingoCallResult::BINGO => Some(i),
             _ => None,
         })
-        .collect()
+        .collect();
+    tracer.trace(
+        format_args!("Called {}: {} new bingos", n, winners.len()),
+        &Boards(boards),
+    );
+    winners
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::parsing::parse_input;
+    use crate::common::trace::NoTracer;
+    use crate::common::trace::RecordingTracer;
+    use crate::Result;
+
+    const TEST_NUMBERS: [u32; 27] = [
+        7, 4, 9, 5, 11, 17, 23, 2, 0, 14, 21, 24, 10, 16, 13, 6, 15, 25, 12, 22, 18, 20, 8, 19, 3,
+        26, 1,
+    ];
+
+    const TEST_BOARDS: [&str; 3] = [
+        "22 13 17 11  0\n 8  2 23  4 24\n21  9 14 16  7\n 6 10  3 18  5\n 1 12 20 15 19",
+        " 3 15  0  2 22\n 9 18 13 17  5\n19  8  7 25 23\n20 11 10 24  4\n14 21 16 12  6",
+        "14 21 17 24  4\n10 16 15  9 19\n18  8 23 26 20\n22 11 13  6  5\n 2  0 12  3  7",
+    ];
+
+    #[test]
+    fn find_winning_bingo_boards_returns_first_winner() -> Result<()> {
+        let winners =
+            find_winning_bingo_boards(TEST_NUMBERS.to_vec(), get_test_boards()?, &mut NoTracer);
+        assert_eq!(1, winners.len());
+        assert_eq!(4512, winners[0].get_score());
+        Ok(())
+    }
+
+    #[test]
+    fn find_losing_bingo_boards_returns_last_winner() -> Result<()> {
+        let losers =
+            find_losing_bingo_boards(TEST_NUMBERS.to_vec(), get_test_boards()?, &mut NoTracer);
+        assert_eq!(1, losers.len());
+        assert_eq!(1924, losers[0].get_score());
+        Ok(())
+    }
+
+    #[test]
+    fn each_call_is_traced() -> Result<()> {
+        let mut tracer = RecordingTracer::default();
+        find_winning_bingo_boards(TEST_NUMBERS.to_vec(), get_test_boards()?, &mut tracer);
+        assert_eq!(12, tracer.events.len());
+        assert_eq!("Called 7: 0 new bingos", tracer.events[0].0);
+        assert_eq!("Called 24: 1 new bingos", tracer.events[11].0);
+        Ok(())
+    }
+
+    #[test]
+    fn games_without_boards_have_no_winners() {
+        assert!(find_winning_bingo_boards(TEST_NUMBERS.to_vec(), vec![], &mut NoTracer).is_empty());
+        assert!(find_losing_bingo_boards(TEST_NUMBERS.to_vec(), vec![], &mut NoTracer).is_empty());
+    }
+
+    fn get_test_boards() -> Result<Vec<BingoBoard>> {
+        TEST_BOARDS
+            .iter()
+            .map(|board| parse_input(board, BingoBoard::from_lines))
+            .collect()
+    }
 }
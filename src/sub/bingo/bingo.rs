@@ -4,11 +4,13 @@ pub enum BingoCallResult {
     NONE,
 }
 
+#[derive(Clone)]
 pub enum BingoTrigger {
     COLUMN(usize),
     ROW(usize),
 }
 
+#[derive(Clone)]
 pub struct Bingo {
     pub final_number: u32,
     pub triggered_by: BingoTrigger,
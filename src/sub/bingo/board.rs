@@ -1,9 +1,11 @@
 use super::bingo::{Bingo, BingoCallResult, BingoTrigger};
-use crate::Result;
-use serde_scan;
+use crate::common::parsing::{int, words};
+use crate::common::InputLine;
+use crate::{Error, Result};
 use std::collections::HashMap;
 use std::fmt::{Display, Formatter};
 
+#[derive(Clone)]
 pub struct BingoBoard {
     numbers: HashMap<u32, [usize; 2]>,
     called_numbers: HashMap<u32, bool>,
@@ -13,15 +15,33 @@ pub struct BingoBoard {
 }
 
 impl BingoBoard {
-    pub fn from_str(s: &str) -> Result<BingoBoard> {
-        let board_data: [[u32; 5]; 5] = serde_scan::from_str(s)?;
+    /// Parses the five rows of five numbers in `lines`.
+    pub fn from_lines(lines: &[InputLine]) -> Result<BingoBoard> {
+        if let Some(extra) = lines.get(5) {
+            return Err(Box::new(extra.error(
+                extra.text.trim(),
+                "Expected a blank line after 5 board rows",
+            )));
+        }
         let mut board_numbers: HashMap<u32, [usize; 2]> = HashMap::new();
-        for y in 0..5 {
-            for x in 0..5 {
-                let number_at_pos: u32 = board_data[y][x];
-                board_numbers.insert(number_at_pos, [x, y]);
+        for (y, line) in lines.iter().enumerate() {
+            let row: Vec<u32> = words(int())(line, line.text)?;
+            if row.len() != 5 {
+                return Err(Box::new(line.error(
+                    line.text.trim(),
+                    &format!("Expected 5 numbers in a board row, found {}", row.len()),
+                )));
+            }
+            for (x, number) in row.into_iter().enumerate() {
+                board_numbers.insert(number, [x, y]);
             }
         }
+        if lines.len() < 5 {
+            return Err(Box::new(match lines.last() {
+                Some(last) => last.error(last.text.trim(), "Expected 5 board rows"),
+                None => Error::parse("No data found for bingo board!"),
+            }));
+        }
         Ok(BingoBoard {
             numbers: board_numbers,
             called_numbers: HashMap::new(),
@@ -156,6 +176,7 @@ impl Display for BingoBoard {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::common::parsing::parse_input;
 
     const TEST_BOARD: &str = "
             22 13 17 11  0
@@ -223,8 +244,20 @@ mod tests {
         assert!(board.has_bingo());
     }
 
+    #[test]
+    fn short_rows_are_reported() {
+        let test_board = TEST_BOARD.replace(" 3 ", " ");
+        let err = parse_input(test_board.trim(), BingoBoard::from_lines)
+            .err()
+            .unwrap();
+        assert_eq!(
+            "Expected 5 numbers in a board row, found 4 at line 4, column 14\n4 |              6 10  18  5\n  |              ^^^^^^^^^^^",
+            err.to_string()
+        );
+    }
+
     fn get_test_board() -> BingoBoard {
-        let board_result = BingoBoard::from_str(TEST_BOARD);
+        let board_result = parse_input(TEST_BOARD.trim(), BingoBoard::from_lines);
         assert!(board_result.is_ok());
         board_result.unwrap()
     }
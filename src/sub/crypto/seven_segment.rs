@@ -1,5 +1,6 @@
-use crate::common::split_lines_on;
-use crate::{Error, Result};
+use crate::common::parsing::{lines, pair, parse_input, words};
+use crate::common::InputLine;
+use crate::Result;
 use std::fmt::{Display, Formatter};
 
 #[derive(Clone)]
@@ -12,12 +13,9 @@ impl OutputDisplay {
         OutputDisplay { digits }
     }
 
-    pub fn from_str(s: &str) -> Result<OutputDisplay> {
-        let segments: Vec<u8> = s
-            .split_whitespace()
-            .map(|word| signal_to_digit(word))
-            .collect::<Result<Vec<u8>>>()?;
-        Ok(OutputDisplay::from(segments))
+    /// Parses the output signals in `s`, which must be a slice of `line`'s text.
+    pub fn from_line(line: &InputLine, s: &str) -> Result<OutputDisplay> {
+        Ok(OutputDisplay::from(words(signal_to_digit)(line, s)?))
     }
 
     pub fn unique_digits(&self) -> Vec<&u8> {
@@ -56,16 +54,16 @@ fn bit_to_segment_letter(bit: u8) -> char {
     }
 }
 
-fn segment_letter_to_bit(letter: char) -> Result<u8> {
+fn segment_letter_to_bit(letter: char) -> Option<u8> {
     match letter {
-        'a' => Ok(0b1000000),
-        'b' => Ok(0b0100000),
-        'c' => Ok(0b0010000),
-        'd' => Ok(0b0001000),
-        'e' => Ok(0b0000100),
-        'f' => Ok(0b0000010),
-        'g' => Ok(0b0000001),
-        _ => Err(Box::new(Error::new(&format!("Unknown char {}!", letter)))),
+        'a' => Some(0b1000000),
+        'b' => Some(0b0100000),
+        'c' => Some(0b0010000),
+        'd' => Some(0b0001000),
+        'e' => Some(0b0000100),
+        'f' => Some(0b0000010),
+        'g' => Some(0b0000001),
+        _ => None,
     }
 }
 
@@ -81,42 +79,34 @@ pub fn digit_to_signal(digit: u8) -> String {
     signal
 }
 
-pub fn signal_to_digit(signal: &str) -> Result<u8> {
-    let digit: Vec<u8> = signal
-        .chars()
-        .map(|c| segment_letter_to_bit(c))
-        .collect::<Result<Vec<u8>>>()?;
-    Ok(digit.iter().fold(0, |acc, bit| acc | bit))
-}
-
-pub fn signals_to_digits(signals: &[&str]) -> Result<Vec<u8>> {
-    signals
-        .iter()
-        .map(|signal| signal_to_digit(signal))
-        .collect()
+/// Parses `signal`, which must be a slice of `line`'s text, pointing at the first letter that
+/// isn't a segment.
+pub fn signal_to_digit(line: &InputLine, signal: &str) -> Result<u8> {
+    signal
+        .char_indices()
+        .try_fold(0, |acc, (i, c)| match segment_letter_to_bit(c) {
+            Some(bit) => Ok(acc | bit),
+            None => Err(line
+                .error(
+                    &signal[i..i + c.len_utf8()],
+                    &format!("Unknown segment {}", c),
+                )
+                .into()),
+        })
 }
 
+/// Reads lines of the form `<signals> | <output>`.
 pub fn signal_output_lines_to_signals_and_output(
-    lines: &str,
+    input: &str,
 ) -> Result<Vec<(Vec<u8>, OutputDisplay)>> {
-    Ok(split_lines_on(lines, " | ")
-        .filter_map(|parts| match parts[..] {
-            [signal_str, output_str] => Some((
-                signal_str.split_whitespace().collect::<Vec<&str>>(),
-                output_str,
-            )),
-            _ => None,
-        })
-        .map(
-            |(signal_vec, output_str)| match signals_to_digits(&signal_vec) {
-                Ok(digits) => match OutputDisplay::from_str(output_str) {
-                    Ok(display) => Ok((digits, display)),
-                    Err(e) => Err(e),
-                },
-                Err(e) => Err(e),
-            },
-        )
-        .collect::<Result<Vec<(Vec<u8>, OutputDisplay)>>>()?)
+    parse_input(
+        input,
+        lines(pair(
+            words(signal_to_digit),
+            " | ",
+            OutputDisplay::from_line,
+        )),
+    )
 }
 
 pub fn count_segments(digit: u8) -> u8 {
@@ -138,8 +128,20 @@ pub fn has_unique_segment_count(digit: u8) -> bool {
 
 #[cfg(test)]
 mod tests {
+    use super::*;
     use crate::sub::crypto::digit_to_signal;
 
+    #[test]
+    fn unknown_segments_are_pointed_at() {
+        let err = signal_output_lines_to_signals_and_output("ab cfg | fdh ab")
+            .err()
+            .unwrap();
+        assert_eq!(
+            "Unknown segment h at line 1, column 12\n1 | ab cfg | fdh ab\n  |            ^",
+            err.to_string()
+        );
+    }
+
     #[test]
     fn digit_to_signal_returns_correct_for_8() {
         assert_eq!("abcdefg", &digit_to_signal(0b1111111));
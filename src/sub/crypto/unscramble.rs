@@ -61,6 +61,8 @@ fn find_decoding_digits(signals: &Vec<u8>) -> Result<[u8; 2]> {
     );
     match digits[..] {
         [Some(one), Some(four)] => Ok([one, four]),
-        _ => Err(Box::new(Error::new("Could not find both digits 1 and 4!"))),
+        _ => Err(Box::new(Error::invalid_data(
+            "Could not find both digits 1 and 4!",
+        ))),
     }
 }
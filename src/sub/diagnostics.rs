@@ -1,37 +1,80 @@
-mod common;
 mod counts;
 mod filters;
 mod frequency;
 
-use crate::Result;
+use crate::common::bit_string::BitString;
+use crate::common::parsing::{lines, parse_input};
+use crate::{Error, Result};
 
-pub fn run_gamma_epsilon_report(binary_numbers: Vec<Vec<u8>>) -> Result<()> {
-    let digit_counts = counts::DigitCounts::from_binary_numbers(&binary_numbers);
+/// Reads one binary number per line. Every number must have as many bits as the first.
+pub fn parse_binary_numbers(input: &str) -> Result<Vec<BitString>> {
+    let numbers = parse_input(input, lines(BitString::parse_line))?;
+    let width = numbers.first().map_or(0, BitString::len);
+    match numbers.iter().position(|number| number.len() != width) {
+        Some(i) => Err(Box::new(Error::invalid_data(&format!(
+            "Binary number {} has {} bits, but the first one has {}!",
+            i + 1,
+            numbers[i].len(),
+            width
+        )))),
+        None => Ok(numbers),
+    }
+}
+
+pub fn run_gamma_epsilon_report(binary_numbers: &[BitString]) -> Result<u32> {
+    let digit_counts = counts::DigitCounts::from_binary_numbers(binary_numbers);
     let gamma_rate = frequency::get_gamma_rate_from_counts(&digit_counts);
     let epsilon_rate = frequency::get_epsilon_rate_from_counts(&digit_counts);
-    println!("Power consumption: {}", gamma_rate * epsilon_rate);
-    Ok(())
+    Ok(rating_value("gamma", &gamma_rate)? * rating_value("epsilon", &epsilon_rate)?)
 }
 
-pub fn run_life_support_rating_report(binary_numbers: Vec<Vec<u8>>) -> Result<()> {
-    let oxygen_rating = filters::find_oxygen_generator_rating(&binary_numbers)?;
-    let co2_rating = filters::find_co2_scrubber_rating(&binary_numbers)?;
-    println!("Life support rating: {}", oxygen_rating * co2_rating);
-    Ok(())
+pub fn run_life_support_rating_report(binary_numbers: &[BitString]) -> Result<u32> {
+    let oxygen_rating = filters::find_oxygen_generator_rating(binary_numbers)?;
+    let co2_rating = filters::find_co2_scrubber_rating(binary_numbers)?;
+    Ok(oxygen_rating * co2_rating)
+}
+
+fn rating_value(label: &str, bits: &BitString) -> Result<u32> {
+    match bits.to_u64().and_then(|value| u32::try_from(value).ok()) {
+        Some(value) => Ok(value),
+        None => Err(Box::new(Error::invalid_data(&format!(
+            "The {} rating {} is too wide for a 32-bit number!",
+            label, bits
+        )))),
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::common::bit_lines;
 
     const TEST_STR: &str =
         "00100\n11110\n10110\n10111\n10101\n01111\n00111\n11100\n10000\n11001\n00010\n01010";
 
     #[test]
-    fn gets_correct_gamma_and_epsilon_for_test_data() {
-        let counts = counts::DigitCounts::from_binary_numbers(&bit_lines(TEST_STR));
-        assert_eq!(22, frequency::get_gamma_rate_from_counts(&counts));
-        assert_eq!(9, frequency::get_epsilon_rate_from_counts(&counts));
+    fn gets_correct_gamma_and_epsilon_for_test_data() -> Result<()> {
+        let counts = counts::DigitCounts::from_binary_numbers(&parse_binary_numbers(TEST_STR)?);
+        assert_eq!(
+            "10110",
+            frequency::get_gamma_rate_from_counts(&counts).to_string()
+        );
+        assert_eq!(
+            "01001",
+            frequency::get_epsilon_rate_from_counts(&counts).to_string()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn reports_are_correct_for_test_data() -> Result<()> {
+        assert_eq!(
+            198,
+            run_gamma_epsilon_report(&parse_binary_numbers(TEST_STR)?)?
+        );
+        assert_eq!(
+            230,
+            run_life_support_rating_report(&parse_binary_numbers(TEST_STR)?)?
+        );
+        Ok(())
     }
 }
@@ -1,85 +1,72 @@
-use super::counts::DigitCounts;
-use super::frequency;
-use crate::sub::diagnostics::common::int_from_bit_iter;
+use super::frequency::{self, choose_bit};
+use super::rating_value;
+use crate::common::bit_string::{count_ones_in_column, BitString};
 use crate::{Error, Result};
 
-pub fn find_oxygen_generator_rating(binary_numbers: &Vec<Vec<u8>>) -> Result<u32> {
+pub fn find_oxygen_generator_rating(binary_numbers: &[BitString]) -> Result<u32> {
     find_system_rating("oxygen generator", binary_numbers, frequency::most_common)
 }
 
-pub fn find_co2_scrubber_rating(binary_numbers: &Vec<Vec<u8>>) -> Result<u32> {
+pub fn find_co2_scrubber_rating(binary_numbers: &[BitString]) -> Result<u32> {
     find_system_rating("CO2 scrubber", binary_numbers, frequency::least_common)
 }
 
 fn find_system_rating(
     system_label: &str,
-    binary_numbers: &Vec<Vec<u8>>,
+    binary_numbers: &[BitString],
     cmp: fn(usize, usize) -> bool,
 ) -> Result<u32> {
-    let first_element = binary_numbers.first();
-    match first_element {
-        Some(_) => match find_bit_vector_using_cmp(binary_numbers, 0, cmp) {
-            Some(co2_rating_vec) => Ok(int_from_bit_iter(co2_rating_vec.iter())),
-            None => Err(Box::new(Error::new(&format!(
-                "Couldn't find {} rating in vector!",
-                system_label
-            )))),
-        },
-        None => Err(Box::new(Error::new(&format!(
+    if binary_numbers.is_empty() {
+        return Err(Box::new(Error::invalid_data(&format!(
             "Empty vector provided to find {} rating!",
             system_label
+        ))));
+    }
+    match find_bit_string_using_cmp(binary_numbers, cmp) {
+        Some(rating) => rating_value(system_label, rating),
+        None => Err(Box::new(Error::invalid_data(&format!(
+            "Couldn't find {} rating in vector!",
+            system_label
         )))),
     }
 }
 
-fn find_bit_vector_using_cmp(
-    binary_numbers: &Vec<Vec<u8>>,
-    current_digit: usize,
+/// Keeps the numbers whose bit in each column, from the left, is the one `cmp` picks for the
+/// numbers still kept, until only one is left.
+fn find_bit_string_using_cmp(
+    binary_numbers: &[BitString],
     cmp: fn(usize, usize) -> bool,
-) -> Option<Vec<u8>> {
-    let counts = DigitCounts::from_binary_numbers(binary_numbers);
-    let first_element = binary_numbers.first();
-    if binary_numbers.len() <= 1 {
-        return first_element.map(|vec_ref| vec_ref.clone());
-    }
-    let filtered_binary_numbers: Vec<Vec<u8>> = binary_numbers
-        .iter()
-        .filter_map(|number_vec| {
-            let actual_digit = number_vec[current_digit];
-            let most_common = counts.value_for(cmp, current_digit);
-            if actual_digit == most_common {
-                Some(number_vec.clone())
-            } else {
-                None
-            }
-        })
-        .collect();
-    if current_digit >= counts.len() {
-        filtered_binary_numbers
-            .first()
-            .map(|vec_ref| vec_ref.clone())
-    } else {
-        find_bit_vector_using_cmp(&filtered_binary_numbers, current_digit + 1, cmp)
+) -> Option<&BitString> {
+    let mut candidates: Vec<&BitString> = binary_numbers.iter().collect();
+    let width = binary_numbers.iter().map(BitString::len).max().unwrap_or(0);
+    for column in 0..width {
+        if candidates.len() <= 1 {
+            break;
+        }
+        let one_count = count_ones_in_column(candidates.iter().copied(), column);
+        let kept_bit = choose_bit(candidates.len() - one_count, one_count, cmp);
+        candidates.retain(|candidate| candidate.get(column) == kept_bit);
     }
+    candidates.first().copied()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::common::bit_lines;
+    use crate::sub::diagnostics::parse_binary_numbers;
 
     const TEST_STR: &str =
         "00100\n11110\n10110\n10111\n10101\n01111\n00111\n11100\n10000\n11001\n00010\n01010";
 
     #[test]
     fn can_find_oxygen_rating() {
-        let result = find_oxygen_generator_rating(&bit_lines(TEST_STR));
+        let result = find_oxygen_generator_rating(&parse_binary_numbers(TEST_STR).unwrap());
         assert!(result.is_ok());
         assert_eq!(23, result.unwrap());
     }
     #[test]
     fn can_find_co2_rating() {
-        let result = find_co2_scrubber_rating(&bit_lines(TEST_STR));
+        let result = find_co2_scrubber_rating(&parse_binary_numbers(TEST_STR).unwrap());
         assert!(result.is_ok());
         assert_eq!(10, result.unwrap());
     }
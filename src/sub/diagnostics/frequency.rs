@@ -1,4 +1,5 @@
 use super::counts::DigitCounts;
+use crate::common::bit_string::BitString;
 
 pub fn least_common(zero_count: usize, one_count: usize) -> bool {
     return zero_count <= one_count;
@@ -8,10 +9,15 @@ pub fn most_common(zero_count: usize, one_count: usize) -> bool {
     return zero_count > one_count;
 }
 
-pub fn get_gamma_rate_from_counts(counts: &DigitCounts) -> u32 {
-    counts.get_int_value_from_comparison(most_common)
+/// The bit that `cmp` picks: 0 when it holds for the counts, 1 otherwise.
+pub fn choose_bit(zero_count: usize, one_count: usize, cmp: fn(usize, usize) -> bool) -> bool {
+    !cmp(zero_count, one_count)
 }
 
-pub fn get_epsilon_rate_from_counts(counts: &DigitCounts) -> u32 {
-    counts.get_int_value_from_comparison(least_common)
+pub fn get_gamma_rate_from_counts(counts: &DigitCounts) -> BitString {
+    counts.bits_from_comparison(most_common)
+}
+
+pub fn get_epsilon_rate_from_counts(counts: &DigitCounts) -> BitString {
+    counts.bits_from_comparison(least_common)
 }
@@ -1,6 +1,7 @@
 pub mod basins;
 mod heightmap;
 
+use crate::common::Tracer;
 use crate::common::Vector2;
 pub use heightmap::Heightmap;
 use std::collections::HashMap;
@@ -40,8 +41,12 @@ pub fn get_risk_levels(heightmap: &Heightmap) -> HashMap<Coords, u8> {
     risk_levels
 }
 
-pub fn get_basin_sizes(heightmap: &Heightmap, sorted: bool) -> Vec<usize> {
-    let basins = basins::find_basins(heightmap);
+pub fn get_basin_sizes<T: Tracer>(
+    heightmap: &Heightmap,
+    sorted: bool,
+    tracer: &mut T,
+) -> Vec<usize> {
+    let basins = basins::find_basins(heightmap, tracer);
     let mut basin_sizes: Vec<usize> = basins.values().map(|basin| basin.len()).collect();
     if sorted {
         basin_sizes.sort_unstable();
@@ -52,8 +57,9 @@ pub fn get_basin_sizes(heightmap: &Heightmap, sorted: bool) -> Vec<usize> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::common::trace::NoTracer;
+    use crate::common::Parse;
     use crate::Result;
-    use std::str::FromStr;
 
     const TEST_STR: &str = "
 2199943210
@@ -65,14 +71,14 @@ mod tests {
 
     #[test]
     fn parsing_heightmap_works() -> Result<()> {
-        let parsed_heightmap = Heightmap::from_str(TEST_STR)?;
+        let parsed_heightmap = Heightmap::parse(TEST_STR)?;
         assert_eq!(Vector2::from(10, 5), parsed_heightmap.size());
         Ok(())
     }
 
     #[test]
     fn find_valleys_works_on_example_data() -> Result<()> {
-        let example_heightmap = Heightmap::from_str(TEST_STR)?;
+        let example_heightmap = Heightmap::parse(TEST_STR)?;
         let expected_valleys: Vec<Coords> = vec![
             Vector2::from(1, 0),
             Vector2::from(9, 0),
@@ -89,8 +95,8 @@ mod tests {
 
     #[test]
     fn find_basin_sizes_works_on_example_data() -> Result<()> {
-        let example_heightmap = Heightmap::from_str(TEST_STR)?;
-        let basin_sizes = get_basin_sizes(&example_heightmap, true);
+        let example_heightmap = Heightmap::parse(TEST_STR)?;
+        let basin_sizes = get_basin_sizes(&example_heightmap, true, &mut NoTracer);
         let expected_sizes: Vec<usize> = vec![3, 9, 9, 14];
         assert_eq!(expected_sizes.len(), basin_sizes.len());
         for (&expected_size, actual_size) in expected_sizes.iter().zip(basin_sizes) {
@@ -101,7 +107,7 @@ mod tests {
 
     #[test]
     fn get_risk_levels_works_on_example_data() -> Result<()> {
-        let example_heightmap = Heightmap::from_str(TEST_STR)?;
+        let example_heightmap = Heightmap::parse(TEST_STR)?;
         let risk_levels = get_risk_levels(&example_heightmap);
         let expected_risk_levels: Vec<(Coords, u8)> = vec![
             (Vector2::from(1, 0), 2),
@@ -1,25 +1,53 @@
 use crate::common::map2d::Cursor;
 use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
 
 use super::find_valleys;
 use super::heightmap::Heightmap;
-use crate::common::Vector2;
+use crate::common::{Tracer, Vector2};
 
 type Coords = Vector2<i64>;
 
-pub fn find_basins(heightmap: &Heightmap) -> HashMap<Coords, Vec<Coords>> {
+/// Finds the basins, tracing the filled area after each round of expansion.
+pub fn find_basins<T: Tracer>(
+    heightmap: &Heightmap,
+    tracer: &mut T,
+) -> HashMap<Coords, Vec<Coords>> {
     let origin_points = find_valleys(heightmap);
     let basins: Vec<Vec<Coords>> = origin_points
         .iter()
         .map(|valley| vec![valley.clone()])
         .collect();
-    fully_expand_basins(heightmap, basins, origin_points)
+    fully_expand_basins(heightmap, basins, origin_points, tracer)
+}
+
+/// The heightmap with every position already in a basin shown as `#`.
+struct BasinFill<'a> {
+    heightmap: &'a Heightmap,
+    visited: &'a HashMap<Coords, ()>,
+}
+
+impl Display for BasinFill<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (height, pos) in self.heightmap.iter() {
+            if pos.x == 0 && pos.y > 0 {
+                writeln!(f)?;
+            }
+            if self.visited.contains_key(&pos) {
+                write!(f, "#")?;
+            } else {
+                write!(f, "{}", height)?;
+            }
+        }
+        writeln!(f)
+    }
 }
 
-fn fully_expand_basins(
+fn fully_expand_basins<T: Tracer>(
     heightmap: &Heightmap,
     mut basins: Vec<Vec<Coords>>,
     origin_points: Vec<Coords>,
+    tracer: &mut T,
 ) -> HashMap<Coords, Vec<Coords>> {
     let mut final_basins: Vec<Vec<Coords>> = vec![];
     for _ in 0..basins.len() {
@@ -27,7 +55,9 @@ fn fully_expand_basins(
     }
     let mut visited: HashMap<Coords, ()> = HashMap::new();
     let mut got_new_positions = true;
+    let mut round = 0;
     while got_new_positions {
+        round += 1;
         let mut new_final_positions = expand_basins(heightmap, &mut basins, &mut visited);
         got_new_positions = new_final_positions
             .iter()
@@ -38,6 +68,13 @@ fn fully_expand_basins(
             .iter_mut()
             .zip(final_basins.iter_mut())
             .for_each(|(from, to)| to.append(from));
+        tracer.trace(
+            format_args!("Basins after round {}: {} positions", round, visited.len()),
+            &BasinFill {
+                heightmap,
+                visited: &visited,
+            },
+        );
     }
     collect_basins_into_map(final_basins, &origin_points)
 }
@@ -105,15 +142,17 @@ fn collect_basins_into_map(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::common::trace::NoTracer;
+    use crate::common::trace::RecordingTracer;
+    use crate::common::Parse;
     use crate::Result;
-    use std::str::FromStr;
 
     const TEST_STR: &str = "2199943210\n3987894921\n9856789892\n8767896789\n9899965678";
 
     #[test]
     fn find_basins_works_on_example_data() -> Result<()> {
-        let example_heightmap = Heightmap::from_str(TEST_STR)?;
-        let basins_map = find_basins(&example_heightmap);
+        let example_heightmap = Heightmap::parse(TEST_STR)?;
+        let basins_map = find_basins(&example_heightmap, &mut NoTracer);
         let expected_basin_sizes: Vec<(Coords, usize)> = vec![
             (Vector2::from(1, 0), 3),
             (Vector2::from(9, 0), 9),
@@ -126,4 +165,15 @@ mod tests {
         }
         Ok(())
     }
+
+    #[test]
+    fn each_expansion_round_is_traced() -> Result<()> {
+        let heightmap = Heightmap::parse("919\n999\n919")?;
+        let mut tracer = RecordingTracer::default();
+        find_basins(&heightmap, &mut tracer);
+        assert_eq!(2, tracer.events.len());
+        assert_eq!("Basins after round 1: 2 positions", tracer.events[0].0);
+        assert_eq!("9#9\n999\n9#9\n", tracer.events[0].1);
+        Ok(())
+    }
 }
@@ -1,8 +1,6 @@
-use std::error::Error;
-use std::str::FromStr;
-
 use crate::common::map2d;
-use crate::common::Vector2;
+use crate::common::{Parse, Vector2};
+use crate::Result;
 
 type Coords = Vector2<i64>;
 
@@ -10,12 +8,10 @@ pub struct Heightmap {
     map2d: map2d::Map2D<u8>,
 }
 
-impl FromStr for Heightmap {
-    type Err = Box<dyn Error>;
-
-    fn from_str(s: &str) -> std::result::Result<Heightmap, Self::Err> {
+impl Parse for Heightmap {
+    fn parse(s: &str) -> Result<Heightmap> {
         Ok(Heightmap {
-            map2d: map2d::Map2D::from_str(s)?,
+            map2d: map2d::Map2D::parse(s)?,
         })
     }
 }
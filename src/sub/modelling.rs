@@ -6,9 +6,15 @@ pub use crate::sub::modelling::crab_subs::FuelModel;
 pub use lanternfish::{Lanternfish, LanternfishPool};
 pub use octopuses::Octopuses;
 
-pub fn simulate_lanternfish(initial_fish: Vec<Lanternfish>, for_days: u32) -> usize {
+use crate::common::Tracer;
+
+pub fn simulate_lanternfish<T: Tracer>(
+    initial_fish: Vec<Lanternfish>,
+    for_days: u32,
+    tracer: &mut T,
+) -> usize {
     let mut pool = LanternfishPool::from(initial_fish);
-    pool.simulate_days(for_days);
+    pool.simulate_days(for_days, tracer);
     pool.num_fish()
 }
 
@@ -19,6 +25,7 @@ pub fn calculate_cheapest_alignment_fuel(crab_subs: Vec<u32>, model: FuelModel)
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::common::trace::NoTracer;
 
     #[test]
     fn calculate_cheapest_alignment_fuel_linear_is_correct_for_example_data() {
@@ -40,7 +47,7 @@ mod tests {
 
     #[test]
     fn simulate_lanternfish_is_correct_for_example_data() {
-        let fish_count = simulate_lanternfish(get_example_fish(), 80);
+        let fish_count = simulate_lanternfish(get_example_fish(), 80, &mut NoTracer);
         assert_eq!(5934, fish_count);
     }
 
@@ -12,7 +12,7 @@ enum TickAction {
     Reproduce,
 }
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Lanternfish {
     pub quantity: usize,
     t_last_reproduction: u32,
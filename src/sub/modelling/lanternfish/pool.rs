@@ -1,6 +1,8 @@
 use super::Lanternfish;
+use crate::common::Tracer;
 use std::cmp::Reverse;
-use std::collections::BinaryHeap;
+use std::collections::{BTreeMap, BinaryHeap};
+use std::fmt::{Display, Formatter};
 
 pub struct LanternfishPool {
     data: BinaryHeap<Reverse<Lanternfish>>,
@@ -30,11 +32,16 @@ impl LanternfishPool {
         self.data.push(Reverse(fish));
     }
 
-    pub fn simulate_days(&mut self, days: u32) {
+    /// Simulates `days` days, tracing the pool at the end of each one.
+    pub fn simulate_days<T: Tracer>(&mut self, days: u32, tracer: &mut T) {
         let final_tick = self.current_tick + days;
         while self.current_tick < final_tick {
             self.process_tick(self.current_tick);
             self.current_tick += 1;
+            tracer.trace(
+                format_args!("Lanternfish after day {}", self.current_tick),
+                self,
+            );
         }
     }
 
@@ -82,9 +89,26 @@ impl LanternfishPool {
     }
 }
 
+impl Display for LanternfishPool {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut by_timer: BTreeMap<u32, usize> = BTreeMap::new();
+        for Reverse(fish) in self.data.iter() {
+            let timer = fish.t_next_reproduction.saturating_sub(self.current_tick);
+            *by_timer.entry(timer).or_insert(0) += fish.quantity;
+        }
+        write!(f, "{} fish;", self.num_fish())?;
+        for (timer, quantity) in by_timer {
+            write!(f, " {} at {}", quantity, timer)?;
+        }
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::common::trace::NoTracer;
+    use crate::common::trace::RecordingTracer;
 
     #[test]
     fn pool_count_initially_zero() {
@@ -109,7 +133,7 @@ mod tests {
     fn fish_on_zero_reproduce() {
         let mut pool = LanternfishPool::from(vec![Lanternfish::from(0)]);
         assert_eq!(1, pool.num_fish());
-        pool.simulate_days(1);
+        pool.simulate_days(1, &mut NoTracer);
         assert_eq!(2, pool.num_fish());
     }
 
@@ -121,7 +145,27 @@ mod tests {
             Lanternfish::from(0),
         ]);
         assert_eq!(3, pool.num_fish());
-        pool.simulate_days(1);
+        pool.simulate_days(1, &mut NoTracer);
         assert_eq!(6, pool.num_fish());
     }
+
+    #[test]
+    fn each_day_is_traced_with_fish_by_timer() {
+        let mut pool = LanternfishPool::from(vec![Lanternfish::from(3), Lanternfish::from(1)]);
+        let mut tracer = RecordingTracer::default();
+        pool.simulate_days(2, &mut tracer);
+        assert_eq!(
+            vec![
+                (
+                    String::from("Lanternfish after day 1"),
+                    String::from("2 fish; 1 at 0 1 at 2")
+                ),
+                (
+                    String::from("Lanternfish after day 2"),
+                    String::from("3 fish; 1 at 1 1 at 6 1 at 8")
+                ),
+            ],
+            tracer.events
+        );
+    }
 }
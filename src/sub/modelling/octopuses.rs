@@ -1,44 +1,65 @@
-use crate::common::{Map2D, Vector2};
-use std::error::Error;
-use std::str::FromStr;
+use crate::common::{Map2D, Parse, Tracer, Vector2};
+use crate::{Error, Result};
+use std::fmt::{Display, Formatter};
 
 type Coords = Vector2<i64>;
 
+#[derive(Clone)]
 pub struct Octopuses {
     map2d: Map2D<u8>,
     flashes: Map2D<bool>,
     total_flash_count: u64,
     last_flash_count: u64,
+    ticks: u64,
 }
 
-impl FromStr for Octopuses {
-    type Err = Box<dyn Error>;
-
-    fn from_str(s: &str) -> std::result::Result<Octopuses, Self::Err> {
-        let main_map = Map2D::from_str(s)?;
+impl Parse for Octopuses {
+    fn parse(s: &str) -> Result<Octopuses> {
+        let main_map = Map2D::parse(s)?;
+        if main_map.width() == 0 {
+            return Err(Box::new(Error::invalid_data(
+                "There are no octopuses in the input!",
+            )));
+        }
         let flash_map = Map2D::new(main_map.size(), false);
         Ok(Octopuses {
             map2d: main_map,
             flashes: flash_map,
             total_flash_count: 0,
             last_flash_count: 0,
+            ticks: 0,
         })
     }
 }
 
 impl Octopuses {
-    pub fn simulate(&mut self, n_ticks: u64) -> u64 {
+    /// How many octopuses there are, which is how many flash on a step when they all do.
+    pub fn count(&self) -> u64 {
+        self.map2d.width() * self.map2d.height()
+    }
+
+    pub fn simulate<T: Tracer>(&mut self, n_ticks: u64, tracer: &mut T) -> u64 {
         for _ in 0..n_ticks {
-            self.last_flash_count = self.tick();
+            self.last_flash_count = self.tick(tracer);
             self.total_flash_count += self.last_flash_count;
         }
         self.total_flash_count
     }
 
-    pub fn tick(&mut self) -> u64 {
+    /// Advances one step and traces the energy levels afterwards.
+    pub fn tick<T: Tracer>(&mut self, tracer: &mut T) -> u64 {
         let initial_flashes = self.increase_all_energy();
         self.cascade_flashes(initial_flashes);
-        self.unflash_all()
+        let flash_count = self.unflash_all();
+        self.ticks += 1;
+        tracer.trace(
+            format_args!(
+                "Octopuses after step {}: {} flashes",
+                self.ticks, flash_count
+            ),
+            self,
+        );
+        flash_count
     }
 
     fn increase_all_energy(&mut self) -> Vec<Coords> {
@@ -111,9 +132,17 @@ impl Octopuses {
     }
 }
 
+impl Display for Octopuses {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.map2d)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::common::trace::NoTracer;
+    use crate::common::trace::RecordingTracer;
     use crate::Result;
 
     #[test]
@@ -125,16 +154,38 @@ mod tests {
 19991
 11111
 ";
-        let mut octopuses = Octopuses::from_str(initial_state)?;
+        let mut octopuses = Octopuses::parse(initial_state)?;
 
-        octopuses.tick();
+        octopuses.tick(&mut NoTracer);
         assert_row_sums(vec![19, 8, 10, 8, 19], &octopuses);
 
-        octopuses.tick();
+        octopuses.tick(&mut NoTracer);
         assert_row_sums(vec![24, 13, 15, 13, 24], &octopuses);
         Ok(())
     }
 
+    #[test]
+    fn each_tick_is_traced() -> Result<()> {
+        let mut octopuses = Octopuses::parse("11111\n19991\n19191\n19991\n11111")?;
+        let mut tracer = RecordingTracer::default();
+
+        octopuses.simulate(2, &mut tracer);
+
+        assert_eq!(2, tracer.events.len());
+        assert_eq!("Octopuses after step 1: 9 flashes", tracer.events[0].0);
+        assert_eq!("34543\n40004\n50005\n40004\n34543\n", tracer.events[0].1);
+        assert_eq!("Octopuses after step 2: 0 flashes", tracer.events[1].0);
+        Ok(())
+    }
+
+    #[test]
+    fn grids_of_any_size_are_counted() -> Result<()> {
+        assert_eq!(1, Octopuses::parse("1")?.count());
+        assert_eq!(6, Octopuses::parse("123\n456")?.count());
+        assert!(Octopuses::parse("\n\n").is_err());
+        Ok(())
+    }
+
     fn assert_row_sums(expected_row_sums: Vec<u64>, octopuses: &Octopuses) {
         for (expected_sum, actual_sum) in expected_row_sums.into_iter().zip(row_sums(octopuses)) {
             assert_eq!(expected_sum, actual_sum)
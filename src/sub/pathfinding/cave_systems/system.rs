@@ -2,10 +2,11 @@ mod connections;
 mod nodes;
 
 use std::collections::HashMap;
-use std::error::Error as ErrorTrait;
 use std::fmt::{Debug, Formatter};
 use std::str::FromStr;
 
+use crate::common::parsing::{lines, pair, parse_input, word};
+use crate::common::Parse;
 use crate::{Error, Result};
 use connections::NodeConnections;
 use nodes::*;
@@ -25,19 +26,16 @@ impl CaveSystem {
         }
     }
 
-    pub fn add_node(&mut self, node_name: &str) -> usize {
+    pub fn add_node(&mut self, node_name: &str) -> Result<usize> {
         match self.nodes_by_name.get(node_name) {
-            Some(node_id) => *node_id,
+            Some(node_id) => Ok(*node_id),
             None => {
-                let new_node = match CaveNode::from_str(node_name) {
-                    Ok(n) => n,
-                    Err(e) => panic!("{}", e),
-                };
+                let new_node = CaveNode::from_str(node_name)?;
                 let new_node_id = self.nodes.len();
                 self.nodes.push(new_node);
                 self.nodes_by_name
                     .insert(String::from(node_name), new_node_id);
-                new_node_id
+                Ok(new_node_id)
             }
         }
     }
@@ -47,41 +45,29 @@ impl CaveSystem {
     }
 }
 
-impl FromStr for CaveSystem {
-    type Err = Box<dyn ErrorTrait>;
-
-    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+impl Parse for CaveSystem {
+    /// Reads one `a-b` connection between two caves per line.
+    fn parse(input: &str) -> Result<Self> {
         let mut cave_system = CaveSystem::new();
-        for line in s.lines() {
-            let [a, b] = parse_cave_names(line)?;
-            let a_id = cave_system.add_node(a);
-            let b_id = cave_system.add_node(b);
+        for (a, b) in parse_input(input, lines(pair(word(), "-", word())))? {
+            let a_id = cave_system.add_node(&a)?;
+            let b_id = cave_system.add_node(&b)?;
             cave_system.add_connection(a_id, b_id);
         }
         Ok(cave_system)
     }
 }
 
-fn parse_cave_names(s: &str) -> Result<[&str; 2]> {
-    match s.split("-").take(2).collect::<Vec<&str>>()[..] {
-        [a, b] => Ok([a, b]),
-        _ => Err(Box::new(Error::new(&format!(
-            "Could not find two cave names in string {}!",
-            s
-        )))),
-    }
-}
-
 impl CaveSystem {
     pub fn find_paths(
         &self,
         from_name: &str,
         to_name: &str,
         allow_double_dip: bool,
-    ) -> Vec<Vec<&str>> {
-        let from = self.get_existing_node_id_by_name(from_name);
-        let to = self.get_existing_node_id_by_name(to_name);
-        self.find_paths_from(from, to, vec![], allow_double_dip)
+    ) -> Result<Vec<Vec<&str>>> {
+        let from = self.get_existing_node_id_by_name(from_name)?;
+        let to = self.get_existing_node_id_by_name(to_name)?;
+        Ok(self.find_paths_from(from, to, vec![], allow_double_dip))
     }
 
     fn find_paths_from(
@@ -147,11 +133,14 @@ impl CaveSystem {
         }
     }
 
-    fn get_existing_node_id_by_name(&self, name: &str) -> usize {
-        *self
-            .nodes_by_name
-            .get(name)
-            .expect(&format!("Expected node \"{}\" to exist!", name))
+    fn get_existing_node_id_by_name(&self, name: &str) -> Result<usize> {
+        match self.nodes_by_name.get(name) {
+            Some(node_id) => Ok(*node_id),
+            None => Err(Box::new(Error::invalid_data(&format!(
+                "No cave named {} in the cave system!",
+                name
+            )))),
+        }
     }
 }
 
@@ -225,7 +214,7 @@ mod tests {
 
     #[test]
     fn can_parse_example_data() -> Result<()> {
-        let cave_system = CaveSystem::from_str(TEST_STR)?;
+        let cave_system = CaveSystem::parse(TEST_STR)?;
         assert_eq!(10, cave_system.nodes.len());
         assert_eq!(18, cave_system.connections.len());
         Ok(())
@@ -234,9 +223,9 @@ mod tests {
     #[test]
     fn can_find_paths_for_minimal_system() -> Result<()> {
         let test_str = "df-start\nzm-end\nstart-zm\nend-df";
-        let cave_system = CaveSystem::from_str(test_str)?;
+        let cave_system = CaveSystem::parse(test_str)?;
         let expected_paths = vec!["start,df,end", "start,zm,end"];
-        let paths = cave_system.find_paths("start", "end", false);
+        let paths = cave_system.find_paths("start", "end", false)?;
         for (mut path, expected_path) in paths.into_iter().zip(expected_paths) {
             path.reverse();
             assert_eq!(expected_path, path.join(","));
@@ -247,8 +236,8 @@ mod tests {
     #[test]
     fn can_find_correct_number_of_paths_through_small_caves() -> Result<()> {
         let test_str = "start-A\nstart-b\nA-c\nA-b\nb-d\nA-end\nb-end";
-        let cave_system = CaveSystem::from_str(test_str)?;
-        let paths = cave_system.find_paths("start", "end", false);
+        let cave_system = CaveSystem::parse(test_str)?;
+        let paths = cave_system.find_paths("start", "end", false)?;
         assert_eq!(10, paths.len());
         Ok(())
     }
@@ -256,9 +245,26 @@ mod tests {
     #[test]
     fn can_find_correct_number_of_paths_through_small_caves_with_double_dip() -> Result<()> {
         let test_str = "start-A\nstart-b\nA-c\nA-b\nb-d\nA-end\nb-end";
-        let cave_system = CaveSystem::from_str(test_str)?;
-        let paths = cave_system.find_paths("start", "end", true);
+        let cave_system = CaveSystem::parse(test_str)?;
+        let paths = cave_system.find_paths("start", "end", true)?;
         assert_eq!(36, paths.len());
         Ok(())
     }
+
+    #[test]
+    fn mixed_case_cave_names_are_invalid_data() {
+        let err = CaveSystem::parse("start-Ab\nAb-end").unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<Error>(),
+            Some(Error::InvalidData(_))
+        ));
+    }
+
+    #[test]
+    fn paths_from_unknown_caves_are_invalid_data() -> Result<()> {
+        let cave_system = CaveSystem::parse("start-A\nA-end")?;
+        let err = cave_system.find_paths("start", "exit", false).unwrap_err();
+        assert_eq!("No cave named exit in the cave system!", err.to_string());
+        Ok(())
+    }
 }
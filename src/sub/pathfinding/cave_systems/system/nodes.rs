@@ -61,9 +61,10 @@ impl FromStr for CaveNode {
                 } else if name == name.to_uppercase() {
                     Ok(CaveNode::large(name))
                 } else {
-                    Err(Box::new(Error::new(
-                        "Cannot create cave from mixed case name!",
-                    )))
+                    Err(Box::new(Error::invalid_data(&format!(
+                        "Cannot create cave from mixed case name {}!",
+                        name
+                    ))))
                 }
             }
         }
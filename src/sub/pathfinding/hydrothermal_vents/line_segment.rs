@@ -1,3 +1,6 @@
+use crate::common::parsing::{int, pair};
+use crate::common::InputLine;
+use crate::Result;
 use micromath::vector::Vector2d;
 use std::iter::{repeat, Map, Repeat, Zip};
 
@@ -11,8 +14,10 @@ impl LineSegment {
         LineSegment { p1, p2 }
     }
 
-    pub fn from_str(s: &str) -> Result<LineSegment, serde_scan::ScanError> {
-        let (x1, y1, x2, y2): (i32, i32, i32, i32) = serde_scan::scan!("{},{} -> {},{}" <- s)?;
+    /// Parses a line of the form `x1,y1 -> x2,y2`.
+    pub fn parse_line(line: &InputLine, text: &str) -> Result<LineSegment> {
+        let point = pair(int(), ",", int());
+        let ((x1, y1), (x2, y2)) = pair(&point, " -> ", &point)(line, text)?;
         Ok(LineSegment::from((
             Vector2d::from((x1, y1)),
             Vector2d::from((x2, y2)),
@@ -77,15 +82,13 @@ fn range_iter(from: i32, to: i32) -> Box<dyn Iterator<Item = i32>> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::common::parsing::{lines, parse_input};
     use crate::sub::pathfinding::tests::{get_example_segments, TEST_STR};
 
     #[test]
     fn can_create_segments_from_str() {
         let example_segments = get_example_segments();
-        let segments_result: Result<Vec<LineSegment>, serde_scan::ScanError> = TEST_STR
-            .lines()
-            .map(|line| LineSegment::from_str(line))
-            .collect();
+        let segments_result = parse_input(TEST_STR, lines(LineSegment::parse_line));
         assert!(segments_result.is_ok());
         let segments = segments_result.unwrap();
         for (expected, actual) in example_segments.iter().zip(segments.iter()) {
@@ -93,4 +96,20 @@ mod tests {
             assert_eq!(expected.p2, actual.p2);
         }
     }
+
+    #[test]
+    fn bad_segments_point_at_the_problem() {
+        let errors: Vec<String> = ["0,9 -> 5,9\n8,0 -> 0,8x", "0,9 -> 5,9\n\n9,4 3,4"]
+            .iter()
+            .filter_map(|input| parse_input(input, lines(LineSegment::parse_line)).err())
+            .map(|err| err.to_string())
+            .collect();
+        assert_eq!(
+            vec![
+                "Could not parse \"8x\" at line 2, column 10\n2 | 8,0 -> 0,8x\n  |          ^^",
+                "Expected \" -> \" at line 3, column 1\n3 | 9,4 3,4\n  | ^^^^^^^"
+            ],
+            errors
+        );
+    }
 }
@@ -1,20 +1,17 @@
 use std::collections::VecDeque;
-use std::num::ParseIntError;
 
 use crate::common;
 
-pub fn run_depth_scan<T>(depth_lines: T, window_size: usize) -> common::Result<()>
+pub fn run_depth_scan<T>(depth_lines: T, window_size: usize) -> common::Result<u32>
 where
-    T: Iterator<Item = Result<i32, ParseIntError>>,
+    T: Iterator<Item = common::Result<i32>>,
 {
-    let num_increases = count_increases_windowed(depth_lines, window_size)?;
-    println!("The depth increased {} times.", num_increases);
-    Ok(())
+    count_increases_windowed(depth_lines, window_size)
 }
 
 fn count_increases_windowed<T>(depths: T, window_size: usize) -> common::Result<u32>
 where
-    T: Iterator<Item = Result<i32, ParseIntError>>,
+    T: Iterator<Item = common::Result<i32>>,
 {
     let mut num_increases = 0;
     let mut window: VecDeque<i32> = VecDeque::new();
@@ -37,19 +34,22 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::common::parsing::{int, lines, parse_input};
+
+    const TEST_STR: &str = "199\n200\n208\n210\n200\n207\n240\n269\n260\n263";
 
     #[test]
     fn count_increases_gets_example_correct() {
-        let test_str = String::from("199\n200\n208\n210\n200\n207\n240\n269\n260\n263");
-        let num_increases = count_increases_windowed(common::int_lines(&test_str), 1);
+        let depths: Vec<i32> = parse_input(TEST_STR, lines(int())).unwrap();
+        let num_increases = count_increases_windowed(depths.into_iter().map(Ok), 1);
         assert!(num_increases.is_ok());
         assert_eq!(7, num_increases.unwrap());
     }
 
     #[test]
     fn windowed_count_increases_gets_example_correct() {
-        let test_str = String::from("199\n200\n208\n210\n200\n207\n240\n269\n260\n263");
-        let num_increases = count_increases_windowed(common::int_lines(&test_str), 3);
+        let depths: Vec<i32> = parse_input(TEST_STR, lines(int())).unwrap();
+        let num_increases = count_increases_windowed(depths.into_iter().map(Ok), 3);
         assert!(num_increases.is_ok());
         assert_eq!(5, num_increases.unwrap())
     }
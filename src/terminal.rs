@@ -0,0 +1,152 @@
+use std::io::Read;
+use std::process::{Command, Stdio};
+
+use crate::{Error, Result};
+
+pub const ENTER_ALTERNATE_SCREEN: &str = "\x1b[?1049h\x1b[?25l";
+pub const LEAVE_ALTERNATE_SCREEN: &str = "\x1b[?25h\x1b[?1049l";
+pub const CLEAR_SCREEN: &str = "\x1b[H\x1b[2J";
+pub const RESET: &str = "\x1b[0m";
+pub const BOLD: &str = "\x1b[1m";
+pub const DIM: &str = "\x1b[2m";
+pub const REVERSE: &str = "\x1b[7m";
+pub const RED: &str = "\x1b[31m";
+pub const GREEN: &str = "\x1b[32m";
+pub const YELLOW: &str = "\x1b[33m";
+
+const ESCAPE: u8 = 0x1b;
+const CTRL_C: u8 = 0x03;
+const CTRL_D: u8 = 0x04;
+
+/// A key press, decoded from the bytes a terminal sends for it.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Key {
+    Up,
+    Down,
+    Left,
+    Right,
+    Enter,
+    Interrupt,
+    Char(char),
+    Unknown,
+}
+
+/// Puts the controlling terminal in raw mode with `stty`, so keys arrive as they are pressed and
+/// are not echoed. The previous settings are restored when this is dropped.
+pub struct RawMode {
+    saved: String,
+}
+
+impl RawMode {
+    pub fn enable() -> Result<RawMode> {
+        let saved = stty(&["-g"])?;
+        stty(&["raw", "-echo"])?;
+        Ok(RawMode {
+            saved: saved.trim().to_string(),
+        })
+    }
+}
+
+impl Drop for RawMode {
+    fn drop(&mut self) {
+        // Nothing more can be done if the terminal cannot be restored.
+        let _ = stty(&[&self.saved]);
+    }
+}
+
+/// Runs `stty` on the terminal attached to stdin and returns what it printed.
+fn stty(args: &[&str]) -> Result<String> {
+    let output = Command::new("stty")
+        .args(args)
+        .stdin(Stdio::inherit())
+        .stderr(Stdio::null())
+        .output()
+        .map_err(|err| Error::io("Could not run stty!", err))?;
+    if !output.status.success() {
+        return Err(Box::new(Error::new("Standard input is not a terminal!")));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// The terminal's size as rows and columns, if it can be found. Pseudo-terminals that were never
+/// given a size report zero.
+pub fn terminal_size() -> Option<(usize, usize)> {
+    let size = stty(&["size"]).ok()?;
+    let (rows, columns) = size.trim().split_once(' ')?;
+    let (rows, columns) = (rows.parse().ok()?, columns.parse().ok()?);
+    (rows > 0 && columns > 0).then_some((rows, columns))
+}
+
+/// Reads the next key press, or `None` when the input has ended.
+pub fn read_key(input: &mut dyn Read) -> Result<Option<Key>> {
+    let byte = match read_byte(input)? {
+        Some(byte) => byte,
+        None => return Ok(None),
+    };
+    let key = match byte {
+        ESCAPE => read_escape_sequence(input)?,
+        b'\r' | b'\n' => Key::Enter,
+        CTRL_C | CTRL_D => Key::Interrupt,
+        byte if byte.is_ascii_graphic() || byte == b' ' => Key::Char(byte as char),
+        _ => Key::Unknown,
+    };
+    Ok(Some(key))
+}
+
+/// Decodes the arrow keys, which arrive as `ESC [ A` or `ESC O A` depending on the terminal's
+/// cursor key mode. Other sequences are read to their final byte and ignored.
+fn read_escape_sequence(input: &mut dyn Read) -> Result<Key> {
+    match read_byte(input)? {
+        Some(b'[') | Some(b'O') => {}
+        _ => return Ok(Key::Unknown),
+    }
+    loop {
+        match read_byte(input)? {
+            Some(b'A') => return Ok(Key::Up),
+            Some(b'B') => return Ok(Key::Down),
+            Some(b'C') => return Ok(Key::Right),
+            Some(b'D') => return Ok(Key::Left),
+            Some(0x40..=0x7e) | None => return Ok(Key::Unknown),
+            Some(_) => {}
+        }
+    }
+}
+
+fn read_byte(input: &mut dyn Read) -> Result<Option<u8>> {
+    let mut byte = [0u8];
+    match input.read(&mut byte) {
+        Ok(0) => Ok(None),
+        Ok(_) => Ok(Some(byte[0])),
+        Err(err) => Err(Box::new(Error::io(
+            "Could not read from the terminal!",
+            err,
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keys_are_decoded() -> Result<()> {
+        let mut input: &[u8] = b"q\x1b[A\x1bOC\r\x1b[1;5D\x1b[5~\x03";
+        let mut keys = vec![];
+        while let Some(key) = read_key(&mut input)? {
+            keys.push(key);
+        }
+        assert_eq!(
+            vec![
+                Key::Char('q'),
+                Key::Up,
+                Key::Right,
+                Key::Enter,
+                Key::Left,
+                Key::Unknown,
+                Key::Interrupt
+            ],
+            keys
+        );
+        Ok(())
+    }
+}